@@ -0,0 +1,148 @@
+use ratatui::style::{Color, Modifier, Style};
+
+// Styled byte ranges of a line, drawn over the line's own style.
+pub type AnsiRanges = Vec<(usize, usize, Style)>;
+
+// Applications often log with ANSI colours. The escape sequences are stripped
+// so search, copy and export see plain text, and the SGR (colour/bold) ones are
+// kept as styled ranges of the stripped text. Other sequences are dropped.
+pub fn parse_ansi(text: &str) -> (String, AnsiRanges) {
+    let mut plain = String::with_capacity(text.len());
+    let mut ranges = Vec::new();
+    let mut style = Style::default();
+    let mut start = 0;
+    let mut rest = text;
+    while let Some(esc) = rest.find('\x1b') {
+        plain.push_str(&rest[..esc]);
+        let Some(sequence) = rest[esc + 1..].strip_prefix('[') else {
+            rest = &rest[esc + 1..];
+            continue;
+        };
+        // parameters run up to a final byte in '@'..='~'
+        let Some(end) = sequence.find(|c: char| ('@'..='~').contains(&c)) else {
+            rest = "";
+            break;
+        };
+        if sequence.as_bytes()[end] == b'm' {
+            let next = apply_sgr(style, &sequence[..end]);
+            if next != style {
+                if plain.len() > start && style != Style::default() {
+                    ranges.push((start, plain.len(), style));
+                }
+                start = plain.len();
+                style = next;
+            }
+        }
+        rest = &sequence[end + 1..];
+    }
+    plain.push_str(rest);
+    if plain.len() > start && style != Style::default() {
+        ranges.push((start, plain.len(), style));
+    }
+    (plain, ranges)
+}
+
+fn apply_sgr(mut style: Style, params: &str) -> Style {
+    // an empty parameter (as in "\x1b[m") means reset; one that isn't a number
+    // we know (None) is skipped rather than treated as a reset
+    let mut codes = params.split(';').map(|code| match code {
+        "" => Some(0),
+        code => code.parse::<u8>().ok(),
+    });
+    while let Some(code) = codes.next() {
+        let Some(code) = code else {
+            continue;
+        };
+        style = match code {
+            0 => Style::default(),
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            7 => style.add_modifier(Modifier::REVERSED),
+            22 => style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style.remove_modifier(Modifier::ITALIC),
+            24 => style.remove_modifier(Modifier::UNDERLINED),
+            27 => style.remove_modifier(Modifier::REVERSED),
+            30..=37 => style.fg(Color::Indexed(code - 30)),
+            90..=97 => style.fg(Color::Indexed(code - 90 + 8)),
+            40..=47 => style.bg(Color::Indexed(code - 40)),
+            100..=107 => style.bg(Color::Indexed(code - 100 + 8)),
+            // back to the line's own colours rather than the terminal's
+            39 => Style { fg: None, ..style },
+            49 => Style { bg: None, ..style },
+            38 | 48 => match extended_color(&mut codes) {
+                Some(color) if code == 38 => style.fg(color),
+                Some(color) => style.bg(color),
+                None => style,
+            },
+            _ => style,
+        };
+    }
+    style
+}
+
+// "5;n" for the 256-colour palette or "2;r;g;b" for truecolour. All of the
+// colour's parameters are consumed even when one of them doesn't parse, so the
+// rest aren't read as codes of their own.
+fn extended_color(codes: &mut impl Iterator<Item = Option<u8>>) -> Option<Color> {
+    match codes.next()? {
+        Some(5) => codes.next()?.map(Color::Indexed),
+        Some(2) => {
+            let (r, g, b) = (codes.next()?, codes.next()?, codes.next()?);
+            Some(Color::Rgb(r?, g?, b?))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colour_codes_are_stripped_and_kept_as_ranges() {
+        let (plain, ranges) = parse_ansi("\x1b[32mINFO\x1b[0m started \x1b[1;31merror\x1b[m");
+        assert_eq!(plain, "INFO started error");
+        assert_eq!(
+            ranges,
+            vec![
+                (0, 4, Style::default().fg(Color::Indexed(2))),
+                (
+                    13,
+                    18,
+                    Style::default()
+                        .add_modifier(Modifier::BOLD)
+                        .fg(Color::Indexed(1))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn other_escape_sequences_are_dropped() {
+        let (plain, ranges) = parse_ansi("\x1b[2Kprogress \x1b[38;5;208m50%\x1b[39m");
+        assert_eq!(plain, "progress 50%");
+        assert_eq!(
+            ranges,
+            vec![(9, 12, Style::default().fg(Color::Indexed(208)))]
+        );
+    }
+
+    #[test]
+    fn out_of_range_codes_leave_the_style_alone() {
+        let (_, ranges) = parse_ansi("\x1b[1mbold \x1b[38;5;300mstill bold\x1b[0m");
+        assert_eq!(
+            ranges,
+            vec![(0, 15, Style::default().add_modifier(Modifier::BOLD))]
+        );
+    }
+
+    #[test]
+    fn a_bad_truecolour_value_swallows_the_whole_colour() {
+        // 1 and 4 are the colour's green and blue, not bold and underline
+        let (plain, ranges) = parse_ansi("\x1b[38;2;300;1;4mplain\x1b[0m");
+        assert_eq!(plain, "plain");
+        assert!(ranges.is_empty());
+    }
+}
@@ -1,35 +1,89 @@
+use crate::ansi::{AnsiRanges, parse_ansi};
+use crate::cli::Cli;
+use crate::config::{
+    Bookmark, Config, PopupConfig, save_bookmarks, save_container_overrides, save_theme,
+};
+use crate::demo;
+use crate::error::AppError;
 use crate::keymaps::{
-    exit_screen_keymaps, log_details_keymaps, main_screen_keymaps, setting_config_keymaps,
+    KeyMap, deployments_keymaps, exit_screen_keymaps, is_force_quit, key_name, log_details_keymaps,
+    log_streams_keymaps, main_screen_keymaps, mouse_events, setting_config_keymaps,
+    stopped_tasks_keymaps,
 };
 use crate::ui::ui;
 
-use crate::aws_utils::{get_clusters, get_log_group_name, get_logs, get_profiles, get_services};
-use aws_config::BehaviorVersion;
-use aws_sdk_ecs::Client;
-use crossterm::event::{self, Event};
+use crate::aws_utils::{
+    CallerIdentity, LogEvent, LogPage, LogStreamInfo, ServiceInfo, TimeWindow, denied_action,
+    describe_failures_message, describe_services_by_name, get_caller_identity, get_clusters,
+    get_log_streams, get_logs, get_profiles, get_services, get_services_with_progress,
+    get_stopped_tasks, get_stream_events, get_task_definition, is_access_denied, is_network_error,
+    is_sso_expired, log_group_console_url, log_groups_from_task_definition, service_info,
+    service_is_degraded, service_is_fargate, services_from_output,
+};
+use arboard::Clipboard;
+use aws_config::{BehaviorVersion, Region, SdkConfig};
+use aws_sdk_ecs::{
+    Client,
+    error::DisplayErrorContext,
+    types::{Cluster, Deployment, Service, Task},
+};
+use chrono::{
+    DateTime, Local, TimeZone, Utc,
+    format::{Item, StrftimeItems},
+};
+use crossterm::{
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
+    execute,
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+};
 use ratatui::{
     Terminal,
+    layout::Rect,
     prelude::Backend,
     style::Color,
     widgets::{ListState, ScrollbarState},
 };
+use regex::{Regex, RegexBuilder, RegexSet};
 use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    error,
+    future::Future,
     io::{self},
-    time::Duration,
+    process::Command,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::{
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    task::JoinHandle,
 };
 
-pub struct OptionList {
-    pub items: Vec<String>,
+const DEFAULT_REGION: &str = "us-east-1";
+const DEPLOYMENT_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+// how long scrolling back through the ECS events holds off their auto-refresh
+const SCROLL_PAUSE: Duration = Duration::from_secs(10);
+const OFFLINE_AFTER_FAILURES: u32 = 3;
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(60);
+const API_LOG_LIMIT: usize = 30;
+// scrolling down to within this many lines of the end prefetches the next pages
+const PREFETCH_MARGIN: usize = 50;
+// Wider JSON values push the columns after them out of line rather than
+// letting one long message pad every row.
+const COLUMN_MAX_WIDTH: usize = 40;
+
+// A selectable list of anything; the UI decides how each item is displayed.
+pub struct OptionList<T> {
+    pub items: Vec<T>,
     pub state: ListState,
 }
 
-impl Default for OptionList {
+impl<T> Default for OptionList<T> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl OptionList {
+impl<T> OptionList<T> {
     pub fn new() -> Self {
         OptionList {
             items: Vec::new(),
@@ -38,6 +92,10 @@ impl OptionList {
     }
 
     pub fn next(&mut self) {
+        // a selection can outlive the items, as when a cluster has no services
+        if self.items.is_empty() {
+            return;
+        }
         let i = match self.state.selected() {
             Some(i) if i < self.items.len() - 1 => i + 1,
             Some(i) => i,
@@ -47,6 +105,9 @@ impl OptionList {
     }
 
     pub fn previous(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
         let i = match self.state.selected() {
             Some(i) if i > 0 => i - 1,
             Some(i) => i,
@@ -55,7 +116,24 @@ impl OptionList {
         self.state.select(Some(i));
     }
 
-    pub fn selected(&self) -> Option<&String> {
+    // PageDown/PageUp: jump by `n` items, stopping at either end.
+    pub fn next_by(&mut self, n: usize) {
+        if self.items.is_empty() {
+            return;
+        }
+        let i = self.state.selected().map_or(0, |i| i.saturating_add(n));
+        self.state.select(Some(i.min(self.items.len() - 1)));
+    }
+
+    pub fn previous_by(&mut self, n: usize) {
+        if self.items.is_empty() {
+            return;
+        }
+        let i = self.state.selected().map_or(0, |i| i.saturating_sub(n));
+        self.state.select(Some(i));
+    }
+
+    pub fn selected(&self) -> Option<&T> {
         if let Some(i) = self.state.selected() {
             self.items.get(i)
         } else {
@@ -64,9 +142,9 @@ impl OptionList {
     }
 }
 
-impl FromIterator<String> for OptionList {
-    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
-        let items: Vec<String> = iter.into_iter().collect();
+impl<T> FromIterator<T> for OptionList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let items: Vec<T> = iter.into_iter().collect();
         let mut state = ListState::default();
         if !items.is_empty() {
             state.select(Some(0));
@@ -80,34 +158,365 @@ pub enum CurrentScreen {
     SettingConfig,
     Exiting,
     LogDetails,
+    Deployments,
+    StoppedTasks,
+    LogStreams,
 }
 
 pub struct ProfileBox {
     pub vertical_scroll_state: ScrollbarState,
-    pub vertical_scroll: usize,
+    pub area: Rect,
 }
 
 pub struct ClusterBox {
     pub vertical_scroll_state: ScrollbarState,
-    pub vertical_scroll: usize,
+    pub area: Rect,
 }
 
 pub struct ServiceBox {
     pub vertical_scroll_state: ScrollbarState,
-    pub vertical_scroll: usize,
+    pub area: Rect,
+}
+
+pub struct FavoriteBox {
+    pub vertical_scroll_state: ScrollbarState,
+    pub area: Rect,
+}
+
+pub struct EcsEventBox {
+    pub vertical_scroll_state: ScrollbarState,
+    pub area: Rect,
 }
 
 pub struct EventLogBox {
     pub vertical_scroll_state: ScrollbarState,
     pub vertical_scroll: usize,
+    pub horizontal_scroll: usize,
+    pub area: Rect,
+    pub saved_position: Option<EventPosition>,
+}
+
+// Where the event list was when it lost focus.
+pub struct EventPosition {
+    events: ListState,
+    wrapped: ListState,
+    horizontal_scroll: usize,
 }
 
 pub enum SettingConfig {
     Profile,
     Cluster,
     Service,
+    Favorites,
+}
+
+// What the main pane lists, switched with `v`.
+pub enum EventSource {
+    Logs,
+    EcsEvents,
+}
+
+// One service's log group and how far into it has been read.
+pub struct LogSource {
+    pub service: String,
+    // (container, log group) for every group being read; more than one when
+    // all of the service's containers are merged
+    pub log_groups: Vec<(String, String)>,
+    pub next_tokens: Vec<Option<String>>,
+    // the container picked with C (None when merging them all), and every
+    // container that has a log group
+    pub container: Option<String>,
+    pub containers: Vec<String>,
+    pub truncated: bool,
+    // desired count and CPU/memory limits, for ECS services
+    pub info: Option<ServiceInfo>,
+}
+
+impl LogSource {
+    fn group_names(&self) -> Vec<String> {
+        self.log_groups
+            .iter()
+            .map(|(_, group)| group.clone())
+            .collect()
+    }
+
+    // The "[tag]" put in front of an event: the service when streams are merged,
+    // the container when several of the service's log groups are.
+    fn tag(&self, event: &LogEvent, merged: bool) -> Option<String> {
+        let container = self
+            .log_groups
+            .iter()
+            .find(|(_, group)| *group == event.log_group)
+            .map(|(container, _)| container)
+            .filter(|_| self.log_groups.len() > 1);
+        match (merged, container) {
+            (true, Some(container)) => Some(format!("{}/{}", self.service, container)),
+            (true, None) => Some(self.service.clone()),
+            (false, Some(container)) => Some(container.clone()),
+            (false, None) => None,
+        }
+    }
+
+    // Every tag this source's events can carry, in order.
+    fn tags(&self, merged: bool) -> Vec<String> {
+        if self.log_groups.len() > 1 {
+            self.log_groups
+                .iter()
+                .map(|(container, _)| match merged {
+                    true => format!("{}/{}", self.service, container),
+                    false => container.clone(),
+                })
+                .collect()
+        } else if merged {
+            vec![self.service.clone()]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+// One line of the event list, or of the ECS events pane. The UI formats it on
+// every frame, so timestamp modes and display formats apply without a reload.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DisplayEvent {
+    pub timestamp: i64,
+    // the service and/or container it came from, when several share the list
+    pub tag: Option<String>,
+    // ANSI codes stripped; `colors` keeps what they styled, relative to it
+    pub message: String,
+    pub colors: AnsiRanges,
+    pub is_stderr: bool,
+}
+
+impl DisplayEvent {
+    // What's drawn after the timestamp.
+    pub fn text(&self) -> String {
+        match &self.tag {
+            Some(tag) => format!("[{}] {}", tag, self.message),
+            None => self.message.clone(),
+        }
+    }
+}
+
+// The log groups and sizing a task definition resolves to.
+#[derive(Clone, Debug)]
+pub struct ResolvedTaskDefinition {
+    // (container, log group) for every container logging to CloudWatch
+    pub log_groups: Vec<(String, String)>,
+    pub info: ServiceInfo,
+}
+
+// Stored in container_overrides to read every container's log group at once.
+pub const ALL_CONTAINERS: &str = "*";
+
+pub enum LaunchTypeFilter {
+    All,
+    Fargate,
+    Ec2,
+}
+
+impl LaunchTypeFilter {
+    pub fn next(&self) -> LaunchTypeFilter {
+        match self {
+            LaunchTypeFilter::All => LaunchTypeFilter::Fargate,
+            LaunchTypeFilter::Fargate => LaunchTypeFilter::Ec2,
+            LaunchTypeFilter::Ec2 => LaunchTypeFilter::All,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LaunchTypeFilter::All => "all",
+            LaunchTypeFilter::Fargate => "Fargate",
+            LaunchTypeFilter::Ec2 => "EC2",
+        }
+    }
+
+    pub fn matches(&self, service: &Service) -> bool {
+        match self {
+            LaunchTypeFilter::All => true,
+            LaunchTypeFilter::Fargate => service_is_fargate(service),
+            LaunchTypeFilter::Ec2 => !service_is_fargate(service),
+        }
+    }
+}
+
+pub enum TimestampMode {
+    Utc,
+    Local,
+    Relative,
+}
+
+impl TimestampMode {
+    pub fn next(&self) -> TimestampMode {
+        match self {
+            TimestampMode::Utc => TimestampMode::Local,
+            TimestampMode::Local => TimestampMode::Relative,
+            TimestampMode::Relative => TimestampMode::Utc,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimestampMode::Utc => "UTC",
+            TimestampMode::Local => "local",
+            TimestampMode::Relative => "relative",
+        }
+    }
+
+    // `custom` is the user's timestamp_format, used for the UTC and local modes.
+    pub fn format(&self, millis: i64, now: DateTime<Utc>, custom: Option<&str>) -> String {
+        let Some(timestamp) = Utc.timestamp_millis_opt(millis).single() else {
+            return millis.to_string();
+        };
+        match self {
+            TimestampMode::Utc => timestamp
+                .format(custom.unwrap_or("%Y-%m-%d %H:%M:%S%.3fZ"))
+                .to_string(),
+            TimestampMode::Local => timestamp
+                .with_timezone(&Local)
+                .format(custom.unwrap_or("%Y-%m-%d %H:%M:%S%.3f"))
+                .to_string(),
+            TimestampMode::Relative => {
+                let seconds = (now - timestamp).num_seconds().max(0);
+                match seconds {
+                    0..60 => format!("{}s ago", seconds),
+                    60..3600 => format!("{}m ago", seconds / 60),
+                    3600..86400 => format!("{}h ago", seconds / 3600),
+                    _ => format!("{}d ago", seconds / 86400),
+                }
+            }
+        }
+    }
+}
+
+// chrono panics while displaying a format string it can't parse, so check it up front.
+pub fn valid_timestamp_format(format: &str) -> bool {
+    !StrftimeItems::new(format).any(|item| matches!(item, Item::Error))
+}
+
+pub enum LogFetchState {
+    // nothing to fetch logs for yet
+    Idle,
+    Fetching,
+    Loaded,
+    Empty,
+}
+
+pub enum DisplayFormat {
+    Full,
+    MessageOnly,
+    TimestampOnly,
+}
+
+impl DisplayFormat {
+    pub fn next(&self) -> DisplayFormat {
+        match self {
+            DisplayFormat::Full => DisplayFormat::MessageOnly,
+            DisplayFormat::MessageOnly => DisplayFormat::TimestampOnly,
+            DisplayFormat::TimestampOnly => DisplayFormat::Full,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DisplayFormat::Full => "full",
+            DisplayFormat::MessageOnly => "message only",
+            DisplayFormat::TimestampOnly => "timestamp only",
+        }
+    }
+
+    // Only changes what is drawn; the stored line keeps both parts.
+    pub fn render(&self, timestamp: &str, message: &str) -> String {
+        match self {
+            DisplayFormat::Full => format!("[{}] {}", timestamp, message),
+            DisplayFormat::MessageOnly => message.to_string(),
+            DisplayFormat::TimestampOnly => format!("[{}]", timestamp),
+        }
+    }
+}
+
+// Result of a picker load running on its own task.
+pub enum Load {
+    Clusters {
+        profile: String,
+        result: Result<Vec<Cluster>, String>,
+    },
+    Services {
+        key: (String, String),
+        // services, plus a note about any ARNs describe_services couldn't resolve
+        result: Result<(Vec<Service>, Option<String>), String>,
+    },
+    // services described so far out of the total listed
+    ServiceProgress {
+        loaded: usize,
+        total: usize,
+    },
+    Identity {
+        profile: String,
+        result: Result<CallerIdentity, String>,
+    },
 }
 
+// Lets a running load report back, tagged with the generation it belongs to.
+#[derive(Clone)]
+pub struct LoadSender {
+    generation: u64,
+    tx: UnboundedSender<(u64, Load)>,
+}
+
+impl LoadSender {
+    pub fn send(&self, load: Load) {
+        let _ = self.tx.send((self.generation, load));
+    }
+}
+
+#[derive(Clone)]
+pub struct ApiCall {
+    pub name: &'static str,
+    pub at: DateTime<Local>,
+    pub duration: Duration,
+    pub ok: bool,
+}
+
+// The last few AWS calls with how long they took, for the Ctrl-D debug overlay.
+// Shared so picker loads on their own task can record theirs too.
+#[derive(Clone, Default)]
+pub struct ApiLog(Arc<Mutex<VecDeque<ApiCall>>>);
+
+impl ApiLog {
+    pub async fn record<T, E>(
+        &self,
+        name: &'static str,
+        call: impl Future<Output = Result<T, E>>,
+    ) -> Result<T, E> {
+        let at = Local::now();
+        let started = Instant::now();
+        let result = call.await;
+        if let Ok(mut calls) = self.0.lock() {
+            if calls.len() == API_LOG_LIMIT {
+                calls.pop_front();
+            }
+            calls.push_back(ApiCall {
+                name,
+                at,
+                duration: started.elapsed(),
+                ok: result.is_ok(),
+            });
+        }
+        result
+    }
+
+    // newest first
+    pub fn calls(&self) -> Vec<ApiCall> {
+        self.0
+            .lock()
+            .map(|calls| calls.iter().rev().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Copy)]
 pub struct Theme {
     pub background: Color,
     pub current_line: Color,
@@ -115,17 +524,22 @@ pub struct Theme {
     pub foreground: Color,
     pub comment: Color,
     pub red: Color,
-    // pub orange: Color,
+    pub orange: Color,
     pub yellow: Color,
     pub green: Color,
-    // pub cyan: Color,
-    // pub purple: Color,
-    // pub pink: Color,
+    pub cyan: Color,
+    pub purple: Color,
+    pub pink: Color,
 }
 
 impl Default for Theme {
     fn default() -> Self {
-        //Dracula Theme
+        Theme::dracula()
+    }
+}
+
+impl Theme {
+    pub fn dracula() -> Theme {
         Theme {
             background: Color::Rgb(40, 42, 54),
             current_line: Color::Rgb(98, 114, 164),
@@ -133,31 +547,259 @@ impl Default for Theme {
             foreground: Color::Rgb(248, 248, 242),
             comment: Color::Rgb(98, 114, 164),
             red: Color::Rgb(255, 85, 85),
-            // orange: Color::Rgb(255, 184, 108),
+            orange: Color::Rgb(255, 184, 108),
             yellow: Color::Rgb(241, 250, 140),
             green: Color::Rgb(80, 250, 123),
-            // cyan: Color::Rgb(139, 233, 253),
-            // purple: Color::Rgb(189, 147, 249),
-            // pink: Color::Rgb(255, 121, 198),
+            cyan: Color::Rgb(139, 233, 253),
+            purple: Color::Rgb(189, 147, 249),
+            pink: Color::Rgb(255, 121, 198),
+        }
+    }
+
+    pub fn solarized_dark() -> Theme {
+        Theme {
+            background: Color::Rgb(0, 43, 54),
+            current_line: Color::Rgb(88, 110, 117),
+            selection: Color::Rgb(7, 54, 66),
+            foreground: Color::Rgb(147, 161, 161),
+            comment: Color::Rgb(88, 110, 117),
+            red: Color::Rgb(220, 50, 47),
+            orange: Color::Rgb(203, 75, 22),
+            yellow: Color::Rgb(181, 137, 0),
+            green: Color::Rgb(133, 153, 0),
+            cyan: Color::Rgb(42, 161, 152),
+            purple: Color::Rgb(108, 113, 196),
+            pink: Color::Rgb(211, 54, 130),
+        }
+    }
+
+    pub fn gruvbox() -> Theme {
+        Theme {
+            background: Color::Rgb(40, 40, 40),
+            current_line: Color::Rgb(102, 92, 84),
+            selection: Color::Rgb(60, 56, 54),
+            foreground: Color::Rgb(235, 219, 178),
+            comment: Color::Rgb(146, 131, 116),
+            red: Color::Rgb(251, 73, 52),
+            orange: Color::Rgb(254, 128, 25),
+            yellow: Color::Rgb(250, 189, 47),
+            green: Color::Rgb(184, 187, 38),
+            cyan: Color::Rgb(142, 192, 124),
+            purple: Color::Rgb(211, 134, 155),
+            pink: Color::Rgb(131, 165, 152),
+        }
+    }
+
+    // Accent colours are darkened well below the dark palettes so text stays
+    // readable on a white background.
+    pub fn light() -> Theme {
+        Theme {
+            background: Color::Rgb(250, 250, 250),
+            current_line: Color::Rgb(200, 210, 230),
+            selection: Color::Rgb(235, 235, 240),
+            foreground: Color::Rgb(40, 42, 54),
+            comment: Color::Rgb(105, 110, 130),
+            red: Color::Rgb(190, 25, 25),
+            orange: Color::Rgb(175, 80, 0),
+            yellow: Color::Rgb(135, 95, 0),
+            green: Color::Rgb(20, 120, 40),
+            cyan: Color::Rgb(0, 110, 140),
+            purple: Color::Rgb(115, 60, 185),
+            pink: Color::Rgb(175, 35, 115),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ThemeName {
+    Dracula,
+    SolarizedDark,
+    Gruvbox,
+    Light,
+}
+
+impl ThemeName {
+    pub fn from_name(name: &str) -> Option<ThemeName> {
+        match name {
+            "dracula" => Some(ThemeName::Dracula),
+            "solarized-dark" => Some(ThemeName::SolarizedDark),
+            "gruvbox" => Some(ThemeName::Gruvbox),
+            "light" => Some(ThemeName::Light),
+            _ => None,
+        }
+    }
+
+    // Terminals such as rxvt and Konsole export COLORFGBG as "fg;bg"; a white
+    // or light grey background index means a light terminal.
+    pub fn detect() -> ThemeName {
+        let background = std::env::var("COLORFGBG")
+            .ok()
+            .and_then(|value| value.rsplit(';').next()?.parse::<u8>().ok());
+        match background {
+            Some(7 | 15) => ThemeName::Light,
+            _ => ThemeName::Dracula,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ThemeName::Dracula => "dracula",
+            ThemeName::SolarizedDark => "solarized-dark",
+            ThemeName::Gruvbox => "gruvbox",
+            ThemeName::Light => "light",
+        }
+    }
+
+    pub fn next(&self) -> ThemeName {
+        match self {
+            ThemeName::Dracula => ThemeName::SolarizedDark,
+            ThemeName::SolarizedDark => ThemeName::Gruvbox,
+            ThemeName::Gruvbox => ThemeName::Light,
+            ThemeName::Light => ThemeName::Dracula,
+        }
+    }
+
+    pub fn theme(&self) -> Theme {
+        match self {
+            ThemeName::Dracula => Theme::dracula(),
+            ThemeName::SolarizedDark => Theme::solarized_dark(),
+            ThemeName::Gruvbox => Theme::gruvbox(),
+            ThemeName::Light => Theme::light(),
         }
     }
 }
 
 pub struct App {
     pub profile: String,
-    pub profiles: OptionList,
+    pub profiles: OptionList<String>,
+    pub profiles_loaded: bool,
     pub cluster: String,
-    pub clusters: OptionList,
-    pub service: String,
-    pub services: OptionList,
-    pub service_events: OptionList,
+    pub clusters: OptionList<String>,
+    pub service: Vec<String>,
+    pub marked_services: Vec<String>,
+    pub services: OptionList<String>,
+    pub services_detail: Vec<Service>,
+    pub sort_services_by_health: bool,
+    pub launch_type_filter: LaunchTypeFilter,
+    pub bookmarks: Vec<Bookmark>,
+    pub container_overrides: HashMap<String, String>,
+    pub theme_name: ThemeName,
+    pub theme: Theme,
+    pub favorites: OptionList<String>,
+    pub status_message: Option<String>,
+    pub picker_error: Option<String>,
+    pub sso_login_needed: bool,
+    pub network_failures: u32,
+    pub retry_at: Option<Instant>,
+    pub run_sso_login: bool,
+    pub deployments: Vec<(String, Vec<Deployment>)>,
+    pub deployments_error: Option<String>,
+    pub deployments_refreshed: Option<Instant>,
+    pub stopped_tasks: Vec<(String, Vec<Task>)>,
+    pub stopped_tasks_error: Option<String>,
+    pub stopped_tasks_refreshed: Option<Instant>,
+    // (log group, stream) when reading a single stream picked with l
+    pub log_stream: Option<(String, String)>,
+    pub log_streams: OptionList<LogStreamInfo>,
+    pub log_streams_group: Option<String>,
+    pub log_streams_error: Option<String>,
+    pub log_streams_refreshed: Option<Instant>,
+    pub crashed_only: bool,
+    pub split_view: bool,
+    pub event_source: EventSource,
+    pub ecs_events: OptionList<DisplayEvent>,
+    pub ecs_events_focused: bool,
+    pub last_user_scroll: Option<Instant>,
+    pub ecs_events_error: Option<String>,
+    pub ecs_events_refreshed: Option<Instant>,
+    pub service_events: OptionList<DisplayEvent>,
     pub current_screen: CurrentScreen,
     pub setting_config: Option<SettingConfig>,
     pub profile_box: ProfileBox,
     pub cluster_box: ClusterBox,
     pub service_box: ServiceBox,
+    pub favorite_box: FavoriteBox,
+    pub ecs_event_box: EcsEventBox,
     pub event_box: EventLogBox,
     pub viewing_logs: bool,
+    pub log_sources: Vec<LogSource>,
+    pub direct_log_group: Option<String>,
+    pub logs_truncated: bool,
+    // tags of the sources in the event list, for colouring merged lines
+    pub source_tags: Vec<String>,
+    pub last_refresh_request: Option<Instant>,
+    pub error_popup: Option<String>,
+    pub mouse_enabled: bool,
+    pub alt_screen: bool,
+    pub show_service_info: bool,
+    pub api_log: ApiLog,
+    pub show_api_log: bool,
+    pub reselect_line: Option<DisplayEvent>,
+    // --demo: canned data instead of AWS calls
+    pub demo: bool,
+    pub popup: PopupConfig,
+    pub profile_filter: Option<String>,
+    pub detail_scroll: u16,
+    // kept alive because on X11 the copied text is served from this handle
+    pub clipboard: Option<Clipboard>,
+    // "profile action" pairs already reported, so periodic refreshes don't
+    // keep reopening the popup
+    pub denied_actions: HashSet<String>,
+    pub load_more_logs: bool,
+    pub prefetch_pages: u32,
+    // pages still to fetch after scrolling near the end
+    pub prefetch_remaining: u32,
+    pub log_error: Option<String>,
+    pub logs_loaded: bool,
+    pub last_refreshed: Option<DateTime<Local>>,
+    pub timestamp_mode: TimestampMode,
+    pub timestamp_format: Option<String>,
+    pub display_format: DisplayFormat,
+    pub time_mark: Option<DateTime<Utc>>,
+    pub wrap_logs: bool,
+    pub wrapped_rows: Vec<usize>,
+    pub wrap_state: ListState,
+    pub stream_prefix: Option<String>,
+    pub prefix_mode: bool,
+    pub prefix_input: String,
+    pub filter_pattern: Option<String>,
+    pub pattern_mode: bool,
+    pub pattern_input: String,
+    pub time_window: TimeWindow,
+    pub window_mode: bool,
+    pub window_input: String,
+    pub search_mode: bool,
+    pub search_query: String,
+    pub use_regex: bool,
+    pub search_regex: Option<Regex>,
+    pub search_error: Option<String>,
+    pub filtered_indices: Vec<usize>,
+    pub hide_health_checks: bool,
+    pub json_columns: Vec<String>,
+    pub show_columns: bool,
+    pub health_checks: RegexSet,
+    pub filtered_state: ListState,
+    pub profile_regions: HashMap<String, String>,
+    pub env_region: Option<String>,
+    pub cluster_cache: HashMap<String, Vec<Cluster>>,
+    pub load_generation: u64,
+    pub load_task: Option<JoinHandle<()>>,
+    pub load_tx: UnboundedSender<(u64, Load)>,
+    pub load_rx: UnboundedReceiver<(u64, Load)>,
+    pub service_load_progress: Option<(usize, usize)>,
+    pub identity: Option<CallerIdentity>,
+    pub identity_profile: Option<String>,
+    // the variable the profile was taken from, until its identity lookup is back
+    pub env_profile_var: Option<&'static str>,
+    pub prod_profiles: HashSet<String>,
+    pub cluster_arns: HashMap<String, String>,
+    pub service_cache: HashMap<(String, String), Vec<Service>>,
+    // Task definition revisions never change, so what they resolve to is kept
+    // per profile and service's task definition ARN; services sharing one only
+    // describe it once.
+    pub task_definition_cache: HashMap<(String, String), ResolvedTaskDefinition>,
+    pub keymap: KeyMap,
+    pub tick_rate: Duration,
 }
 
 impl Default for App {
@@ -168,51 +810,253 @@ impl Default for App {
 
 impl App {
     pub fn new() -> App {
+        let (load_tx, load_rx) = mpsc::unbounded_channel();
         App {
             profile: String::new(),
             profiles: OptionList::new(),
+            profiles_loaded: false,
             cluster: String::new(),
             clusters: OptionList::new(),
-            service: String::new(),
+            service: Vec::new(),
+            marked_services: Vec::new(),
             services: OptionList::new(),
+            services_detail: Vec::new(),
+            sort_services_by_health: false,
+            launch_type_filter: LaunchTypeFilter::All,
+            bookmarks: Vec::new(),
+            container_overrides: HashMap::new(),
+            theme_name: ThemeName::Dracula,
+            theme: Theme::dracula(),
+            favorites: OptionList::new(),
+            status_message: None,
+            picker_error: None,
+            sso_login_needed: false,
+            network_failures: 0,
+            retry_at: None,
+            run_sso_login: false,
+            deployments: Vec::new(),
+            deployments_error: None,
+            deployments_refreshed: None,
+            stopped_tasks: Vec::new(),
+            stopped_tasks_error: None,
+            stopped_tasks_refreshed: None,
+            log_stream: None,
+            log_streams: OptionList::new(),
+            log_streams_group: None,
+            log_streams_error: None,
+            log_streams_refreshed: None,
+            crashed_only: false,
+            split_view: false,
+            event_source: EventSource::Logs,
+            ecs_events: OptionList::new(),
+            ecs_events_focused: false,
+            last_user_scroll: None,
+            ecs_events_error: None,
+            ecs_events_refreshed: None,
             service_events: OptionList::new(),
             current_screen: CurrentScreen::Main,
             setting_config: None,
             profile_box: ProfileBox {
                 vertical_scroll_state: ScrollbarState::default(),
-                vertical_scroll: 0,
+                area: Rect::default(),
             },
             cluster_box: ClusterBox {
                 vertical_scroll_state: ScrollbarState::default(),
-                vertical_scroll: 0,
+                area: Rect::default(),
             },
             service_box: ServiceBox {
                 vertical_scroll_state: ScrollbarState::default(),
-                vertical_scroll: 0,
+                area: Rect::default(),
+            },
+            favorite_box: FavoriteBox {
+                vertical_scroll_state: ScrollbarState::default(),
+                area: Rect::default(),
+            },
+            ecs_event_box: EcsEventBox {
+                vertical_scroll_state: ScrollbarState::default(),
+                area: Rect::default(),
             },
             event_box: EventLogBox {
                 vertical_scroll_state: ScrollbarState::default(),
                 vertical_scroll: 0,
+                horizontal_scroll: 0,
+                area: Rect::default(),
+                saved_position: None,
             },
             viewing_logs: false,
+            log_sources: Vec::new(),
+            source_tags: Vec::new(),
+            last_refresh_request: None,
+            error_popup: None,
+            mouse_enabled: true,
+            alt_screen: true,
+            show_service_info: false,
+            api_log: ApiLog::default(),
+            show_api_log: false,
+            reselect_line: None,
+            demo: false,
+            popup: PopupConfig::default(),
+            profile_filter: None,
+            detail_scroll: 0,
+            clipboard: None,
+            denied_actions: HashSet::new(),
+            direct_log_group: None,
+            logs_truncated: false,
+            load_more_logs: false,
+            prefetch_pages: 1,
+            prefetch_remaining: 0,
+            log_error: None,
+            logs_loaded: false,
+            last_refreshed: None,
+            timestamp_mode: TimestampMode::Utc,
+            timestamp_format: None,
+            display_format: DisplayFormat::Full,
+            time_mark: None,
+            wrap_logs: false,
+            wrapped_rows: Vec::new(),
+            wrap_state: ListState::default(),
+            stream_prefix: None,
+            prefix_mode: false,
+            prefix_input: String::new(),
+            filter_pattern: None,
+            pattern_mode: false,
+            pattern_input: String::new(),
+            time_window: TimeWindow::default(),
+            window_mode: false,
+            window_input: String::new(),
+            search_mode: false,
+            search_query: String::new(),
+            use_regex: false,
+            search_regex: None,
+            search_error: None,
+            filtered_indices: Vec::new(),
+            hide_health_checks: false,
+            json_columns: Vec::new(),
+            show_columns: true,
+            health_checks: RegexSet::empty(),
+            filtered_state: ListState::default(),
+            profile_regions: HashMap::new(),
+            env_region: ["AWS_REGION", "AWS_DEFAULT_REGION"]
+                .iter()
+                .find_map(|var| std::env::var(var).ok().filter(|value| !value.is_empty())),
+            cluster_cache: HashMap::new(),
+            load_generation: 0,
+            load_task: None,
+            load_tx,
+            load_rx,
+            service_load_progress: None,
+            identity: None,
+            identity_profile: None,
+            env_profile_var: None,
+            prod_profiles: HashSet::new(),
+            cluster_arns: HashMap::new(),
+            service_cache: HashMap::new(),
+            task_definition_cache: HashMap::new(),
+            keymap: KeyMap::default(),
+            tick_rate: Duration::from_millis(250),
         }
     }
 
+    pub fn with_config(config: &Config) -> Result<App, Box<dyn error::Error>> {
+        if config.tick_rate_ms == 0 {
+            return Err("tick_rate_ms must be greater than zero".into());
+        }
+        if config
+            .popup
+            .columns
+            .iter()
+            .map(|&c| u32::from(c))
+            .sum::<u32>()
+            > 100
+        {
+            return Err("popup columns must add up to 100 or less".into());
+        }
+        let theme_name = match config.theme.as_deref() {
+            None => ThemeName::detect(),
+            Some(name) => {
+                ThemeName::from_name(name).ok_or_else(|| format!("unknown theme '{}'", name))?
+            }
+        };
+        let timestamp_format = config
+            .timestamp_format
+            .clone()
+            .filter(|format| valid_timestamp_format(format));
+        let status_message = config
+            .timestamp_format
+            .as_ref()
+            .filter(|_| timestamp_format.is_none())
+            .map(|format| {
+                format!(
+                    "Invalid timestamp_format '{}' in config, using the default",
+                    format
+                )
+            });
+        let health_checks = RegexSet::new(&config.health_check_patterns)
+            .map_err(|e| format!("invalid health_check_patterns: {}", e))?;
+        let mut app = App {
+            keymap: KeyMap::from_config(&config.keys)?,
+            health_checks,
+            json_columns: config.columns.clone(),
+            timestamp_format,
+            status_message,
+            theme_name,
+            theme: theme_name.theme(),
+            tick_rate: Duration::from_millis(config.tick_rate_ms),
+            bookmarks: config.bookmarks.clone(),
+            container_overrides: config.container_overrides.clone(),
+            prod_profiles: config.prod_profiles.clone(),
+            mouse_enabled: config.mouse,
+            popup: config.popup,
+            prefetch_pages: config.prefetch_pages,
+            ..App::new()
+        };
+        app.rebuild_favorites();
+        Ok(app)
+    }
+
     pub async fn run_app<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<bool> {
-        let tick_rate = Duration::from_millis(250);
+        let tick_rate = self.tick_rate;
         let mut last_tick = std::time::Instant::now();
         let mut dirty = true;
         loop {
             let timeout = tick_rate.saturating_sub(last_tick.elapsed());
             if event::poll(timeout)? {
-                if let Event::Key(key) = event::read()? {
+                let event = event::read()?;
+                if let Event::Mouse(mouse) = event
+                    && self.mouse_enabled
+                {
+                    mouse_events(mouse, self);
+                    dirty = true;
+                }
+                // draw the new layout straight away rather than on the next tick
+                if let Event::Resize(..) = event {
+                    dirty = true;
+                }
+                if let Event::Key(key) = event {
                     if key.kind == event::KeyEventKind::Release {
                         // Skip events that are not KeyEventKind::Press
                         continue;
                     }
+                    if is_force_quit(&key) {
+                        return Ok(true);
+                    }
+                    // the error popup swallows the key that dismisses it
+                    if self.error_popup.take().is_some() {
+                        dirty = true;
+                        continue;
+                    }
+                    if key.code == KeyCode::Char('d')
+                        && key.modifiers.contains(KeyModifiers::CONTROL)
+                    {
+                        self.show_api_log = !self.show_api_log;
+                        dirty = true;
+                        continue;
+                    }
                     match self.current_screen {
                         CurrentScreen::Main => {
-                            main_screen_keymaps(key, self);
+                            if main_screen_keymaps(key, self)? {
+                                return Ok(true);
+                            }
                         }
                         CurrentScreen::Exiting => {
                             if exit_screen_keymaps(key, self)? {
@@ -222,10 +1066,23 @@ impl App {
                         CurrentScreen::LogDetails => {
                             log_details_keymaps(key, self);
                         }
+                        CurrentScreen::Deployments => {
+                            deployments_keymaps(key, self);
+                        }
+                        CurrentScreen::StoppedTasks => {
+                            stopped_tasks_keymaps(key, self);
+                        }
+                        CurrentScreen::LogStreams => {
+                            log_streams_keymaps(key, self);
+                        }
                         CurrentScreen::SettingConfig => {
                             setting_config_keymaps(key, self);
                         }
                     }
+                    if self.run_sso_login {
+                        self.run_sso_login = false;
+                        self.sso_login(terminal)?;
+                    }
                     dirty = true;
                 }
             }
@@ -244,128 +1101,1916 @@ impl App {
     }
 
     async fn on_tick(&mut self) {
+        self.receive_loads();
+        if self.demo {
+            self.demo_tick();
+            return;
+        }
+        // Network failures clear themselves once the backoff runs out so the
+        // failed call is retried; anything else waits for a manual refresh.
+        match self.retry_at {
+            Some(retry_at) if Instant::now() < retry_at => return,
+            Some(_) => {
+                self.retry_at = None;
+                self.log_error = None;
+                self.picker_error = None;
+                self.deployments_refreshed = None;
+                if self.identity.is_none() {
+                    self.identity_profile = None;
+                }
+            }
+            None => {}
+        }
+        // looked up once per profile so the header can show which account is live;
+        // it runs on its own task so the STS round trip doesn't hold up the UI
+        if !self.profile.is_empty() && self.identity_profile.as_ref() != Some(&self.profile) {
+            self.identity_profile = Some(self.profile.clone());
+            self.identity = None;
+            let aws_config = self.sdk_config().await;
+            let profile = self.profile.clone();
+            let api_log = self.api_log.clone();
+            let sender = LoadSender {
+                generation: self.load_generation,
+                tx: self.load_tx.clone(),
+            };
+            tokio::spawn(async move {
+                let result = api_log
+                    .record("GetCallerIdentity", get_caller_identity(&aws_config))
+                    .await
+                    .map_err(|e| DisplayErrorContext(e).to_string());
+                sender.send(Load::Identity { profile, result });
+            });
+        }
+        if let CurrentScreen::Main = &self.current_screen {
+            if self.has_log_target() && !self.logs_loaded && self.log_error.is_none() {
+                // Network failures are retried once their backoff runs out; anything
+                // else waits for a refresh so an idle viewer stops making API calls.
+                match self.fetch_logs().await {
+                    Ok((sources, events)) => {
+                        self.network_failures = 0;
+                        self.logs_truncated = sources.iter().any(|source| source.truncated);
+                        self.source_tags = sources
+                            .iter()
+                            .flat_map(|source| source.tags(self.service.len() > 1))
+                            .collect();
+                        self.log_sources = sources;
+                        self.logs_loaded = true;
+                        self.last_refreshed = Some(Local::now());
+                        self.service_events = OptionList::from_iter(events);
+                        let last = self.service_events.items.len().saturating_sub(1);
+                        let selected = self
+                            .reselect_line
+                            .take()
+                            .and_then(|line| {
+                                self.service_events
+                                    .items
+                                    .iter()
+                                    .position(|item| *item == line)
+                            })
+                            .unwrap_or(last);
+                        self.event_box.vertical_scroll_state = self
+                            .event_box
+                            .vertical_scroll_state
+                            .content_length(self.service_events.items.len())
+                            .position(selected);
+                        self.service_events.state.select(Some(selected));
+                        self.refilter();
+                    }
+                    Err(e) => {
+                        self.note_failure(&e);
+                        self.log_error = Some(e);
+                    }
+                }
+            }
+        }
+        if let CurrentScreen::Deployments = &self.current_screen {
+            let stale = self
+                .deployments_refreshed
+                .is_none_or(|refreshed| refreshed.elapsed() >= DEPLOYMENT_REFRESH_INTERVAL);
+            if stale && !self.service.is_empty() {
+                let aws_config = self.sdk_config().await;
+                let ecs_client = aws_sdk_ecs::Client::new(&aws_config);
+                match self
+                    .api_log
+                    .record(
+                        "DescribeServices",
+                        describe_services_by_name(&ecs_client, self.cluster_id(), &self.service),
+                    )
+                    .await
+                {
+                    Ok(services) if services.is_empty() => {
+                        self.deployments.clear();
+                        self.deployments_error =
+                            Some(format!("Service {} not found", self.service.join(", ")));
+                    }
+                    Ok(services) => {
+                        self.network_failures = 0;
+                        self.deployments = services
+                            .iter()
+                            .map(|service| {
+                                (
+                                    service.service_name().unwrap_or_default().to_string(),
+                                    service.deployments().to_vec(),
+                                )
+                            })
+                            .collect();
+                        self.deployments_error = None;
+                    }
+                    Err(e) => {
+                        let message = self.aws_error_message(&e);
+                        self.note_failure(&message);
+                        self.deployments_error = Some(message);
+                    }
+                }
+                self.deployments_refreshed = Some(Instant::now());
+            }
+        }
+        if let CurrentScreen::StoppedTasks = &self.current_screen {
+            let stale = self
+                .stopped_tasks_refreshed
+                .is_none_or(|refreshed| refreshed.elapsed() >= DEPLOYMENT_REFRESH_INTERVAL);
+            if stale && !self.service.is_empty() {
+                let aws_config = self.sdk_config().await;
+                let ecs_client = aws_sdk_ecs::Client::new(&aws_config);
+                let mut stopped_tasks = Vec::new();
+                self.stopped_tasks_error = None;
+                for name in self.service.clone() {
+                    match self
+                        .api_log
+                        .record(
+                            "ListTasks + DescribeTasks",
+                            get_stopped_tasks(&ecs_client, self.cluster_id(), &name),
+                        )
+                        .await
+                    {
+                        Ok(tasks) => stopped_tasks.push((name, tasks)),
+                        Err(e) => {
+                            let message = self.aws_error_message(&e);
+                            self.note_failure(&message);
+                            self.stopped_tasks_error = Some(message);
+                            break;
+                        }
+                    }
+                }
+                self.stopped_tasks = stopped_tasks;
+                self.stopped_tasks_refreshed = Some(Instant::now());
+            }
+        }
+        if let (CurrentScreen::LogStreams, Some(log_group)) =
+            (&self.current_screen, &self.log_streams_group)
+            && self.log_streams_refreshed.is_none()
+        {
+            let log_group = log_group.clone();
+            let aws_config = self.sdk_config().await;
+            let cw_client = aws_sdk_cloudwatchlogs::Client::new(&aws_config);
+            match self
+                .api_log
+                .record(
+                    "DescribeLogStreams",
+                    get_log_streams(&cw_client, &log_group),
+                )
+                .await
+            {
+                Ok(streams) => {
+                    self.log_streams = OptionList::from_iter(streams);
+                    self.log_streams_error = None;
+                }
+                Err(e) => {
+                    let message = self.aws_error_message(&e);
+                    self.note_failure(&message);
+                    self.log_streams_error = Some(message);
+                }
+            }
+            self.log_streams_refreshed = Some(Instant::now());
+        }
         if let CurrentScreen::Main = &self.current_screen {
-            if !self.profile.is_empty()
-                && !self.cluster.is_empty()
+            let stale = self
+                .ecs_events_refreshed
+                .is_none_or(|refreshed| refreshed.elapsed() >= DEPLOYMENT_REFRESH_INTERVAL);
+            if self.ecs_events_shown()
+                && stale
+                && !self.ecs_refresh_paused()
                 && !self.service.is_empty()
-                && self.service_events.items.is_empty()
             {
-                let aws_config = aws_config::defaults(BehaviorVersion::latest())
-                    .region("us-east-1")
-                    .profile_name(&self.profile)
-                    .load()
-                    .await;
+                let aws_config = self.sdk_config().await;
                 let ecs_client = aws_sdk_ecs::Client::new(&aws_config);
-                let cw_client = aws_sdk_cloudwatchlogs::Client::new(&aws_config);
-                if let Ok(service) = get_services(&ecs_client, &self.cluster).await {
-                    if let Some(services) = service.services {
-                        if let Some(service_obj) = services
-                            .iter()
-                            .find(|s| s.service_name().unwrap_or_default() == self.service)
-                        {
-                            let log_group = get_log_group_name(&ecs_client, service_obj).await;
-                            if let Ok(events) = get_logs(&cw_client, &log_group.unwrap()).await {
-                                self.service_events = OptionList::from_iter(events);
-                                self.event_box.vertical_scroll_state = self
-                                    .event_box
-                                    .vertical_scroll_state
-                                    .content_length(self.service_events.items.len())
-                                    .position(self.service_events.items.len());
-                                self.service_events.state.select(Some(
-                                    self.service_events.items.len().saturating_sub(1),
-                                ));
-                            }
+                match self
+                    .api_log
+                    .record(
+                        "DescribeServices",
+                        describe_services_by_name(&ecs_client, self.cluster_id(), &self.service),
+                    )
+                    .await
+                {
+                    Ok(services) => {
+                        self.network_failures = 0;
+                        self.set_ecs_events(&services);
+                        self.ecs_events_error = None;
+                    }
+                    Err(e) => {
+                        let message = self.aws_error_message(&e);
+                        self.note_failure(&message);
+                        self.ecs_events_error = Some(message);
+                    }
+                }
+                self.ecs_events_refreshed = Some(Instant::now());
+            }
+        }
+        if self.prefetch_remaining > 0 && !self.load_more_logs {
+            self.prefetch_remaining -= 1;
+            self.load_more_logs = self.logs_truncated;
+        }
+        if self.load_more_logs {
+            self.load_more_logs = false;
+            let aws_config = self.sdk_config().await;
+            let cw_client = aws_sdk_cloudwatchlogs::Client::new(&aws_config);
+            let merged = self.log_sources.len() > 1;
+            let mut events = Vec::new();
+            let mut failure = None;
+            for source in &mut self.log_sources {
+                // groups that are already exhausted would start over from the top
+                let (groups, next_tokens): (Vec<String>, Vec<Option<String>>) = source
+                    .group_names()
+                    .into_iter()
+                    .zip(source.next_tokens.iter().cloned())
+                    .filter(|(_, next_token)| next_token.is_some())
+                    .unzip();
+                if groups.is_empty() {
+                    continue;
+                }
+                let prefix = self.stream_prefix.as_deref();
+                match self
+                    .api_log
+                    .record(
+                        "FilterLogEvents",
+                        get_logs(
+                            &cw_client,
+                            &groups,
+                            prefix,
+                            self.filter_pattern.as_deref(),
+                            self.time_window,
+                            &next_tokens,
+                        ),
+                    )
+                    .await
+                {
+                    Ok(page) => {
+                        events.extend(page_events(&page, source, merged));
+                        let mut returned = page.next_tokens.into_iter();
+                        for next_token in source.next_tokens.iter_mut().filter(|t| t.is_some()) {
+                            *next_token = returned.next().flatten();
                         }
+                        source.truncated = page.truncated;
+                    }
+                    Err(e) => {
+                        failure = Some(e);
+                        break;
                     }
                 }
             }
+            // shown like a failed first page, and a prefetch stops rather than
+            // spending its remaining pages on the same error
+            if let Some(e) = failure {
+                let message = self.aws_error_message(&e);
+                self.note_failure(&message);
+                self.log_error = Some(message);
+                self.prefetch_remaining = 0;
+            }
+            self.logs_truncated = self.log_sources.iter().any(|source| source.truncated);
+            self.service_events.items.extend(events);
+            if !self.source_tags.is_empty() {
+                self.service_events
+                    .items
+                    .sort_by_key(|event| event.timestamp);
+            }
+            self.last_refreshed = Some(Local::now());
+            self.event_box.vertical_scroll_state = self
+                .event_box
+                .vertical_scroll_state
+                .content_length(self.service_events.items.len());
+            self.refilter();
         }
         if let CurrentScreen::SettingConfig = &self.current_screen {
-            if !self.service_events.items.is_empty() {
-                self.service_events = OptionList::new();
+            // a stream picked for the old selection means nothing for the new one
+            self.log_stream = None;
+            if self.logs_loaded {
+                self.reset_logs();
             }
         }
         if let Some(setting_config) = &self.setting_config {
             match setting_config {
                 SettingConfig::Profile => {
-                    if self.profiles.items.is_empty() {
+                    if !self.profiles_loaded && self.picker_error.is_none() {
                         // Load profiles if not already loaded
-                        let profiles = get_profiles().await.unwrap();
-                        self.profiles = OptionList::from_iter(profiles);
-                        self.profile_box.vertical_scroll_state = self
-                            .profile_box
-                            .vertical_scroll_state
-                            .content_length(self.profiles.items.len());
+                        let profiles = match get_profiles().await {
+                            Ok(profiles) => profiles,
+                            Err(e) => {
+                                self.picker_error = Some(e.to_string());
+                                return;
+                            }
+                        };
+                        self.set_profiles(profiles);
                     }
                 }
                 SettingConfig::Cluster => {
-                    if !self.profile.is_empty() && self.clusters.items.is_empty() {
-                        let aws_config = aws_config::defaults(BehaviorVersion::latest())
-                            .region("us-east-1")
-                            .profile_name(self.profiles.selected().unwrap())
-                            .load()
-                            .await;
-                        let client = Client::new(&aws_config);
-                        let cluster = get_clusters(&client).await.unwrap();
-                        self.clusters = OptionList::from_iter(
-                            cluster
-                                .clusters
-                                .unwrap()
-                                .iter()
-                                .map(|c| c.cluster_name().unwrap().to_string())
-                                .collect::<Vec<String>>(),
-                        );
-                        self.cluster_box.vertical_scroll_state = self
-                            .cluster_box
-                            .vertical_scroll_state
-                            .content_length(self.clusters.items.len());
+                    if !self.profile.is_empty()
+                        && self.clusters.items.is_empty()
+                        && self.picker_error.is_none()
+                        && !self.load_pending()
+                    {
+                        match self.cluster_cache.get(&self.profile).cloned() {
+                            Some(clusters) => self.set_clusters(&clusters),
+                            None => {
+                                let aws_config = self.sdk_config().await;
+                                let profile = self.profile.clone();
+                                let api_log = self.api_log.clone();
+                                self.spawn_load(|_| async move {
+                                    let client = Client::new(&aws_config);
+                                    let result = api_log
+                                        .record(
+                                            "ListClusters + DescribeClusters",
+                                            get_clusters(&client),
+                                        )
+                                        .await
+                                        .map_err(|e| DisplayErrorContext(e).to_string());
+                                    Load::Clusters { profile, result }
+                                });
+                            }
+                        }
                     }
                 }
                 SettingConfig::Service => {
                     if !self.profile.is_empty()
                         && !self.cluster.is_empty()
                         && self.services.items.is_empty()
+                        && self.picker_error.is_none()
+                        && !self.load_pending()
                     {
-                        let aws_config = aws_config::defaults(BehaviorVersion::latest())
-                            .region("us-east-1")
-                            .profile_name(self.profiles.selected().unwrap())
-                            .load()
-                            .await;
-                        let client = Client::new(&aws_config);
-                        let service = get_services(&client, &self.cluster).await.unwrap();
-                        self.services = OptionList::from_iter(
-                            service
-                                .services
-                                .unwrap()
-                                .iter()
-                                .map(|s| s.service_name().unwrap().to_string())
-                                .collect::<Vec<String>>(),
-                        );
-                        self.service_box.vertical_scroll_state = self
-                            .service_box
-                            .vertical_scroll_state
-                            .content_length(self.services.items.len());
+                        let cache_key = (self.profile.clone(), self.cluster.clone());
+                        match self.service_cache.get(&cache_key).cloned() {
+                            Some(services) => {
+                                self.services_detail = services;
+                                self.rebuild_services();
+                            }
+                            None => {
+                                let aws_config = self.sdk_config().await;
+                                let cluster_id = self.cluster_id().to_string();
+                                let api_log = self.api_log.clone();
+                                self.spawn_load(|sender| async move {
+                                    let client = Client::new(&aws_config);
+                                    let result = api_log
+                                        .record(
+                                            "ListServices + DescribeServices",
+                                            get_services_with_progress(
+                                                &client,
+                                                &cluster_id,
+                                                |loaded, total| {
+                                                    sender.send(Load::ServiceProgress {
+                                                        loaded,
+                                                        total,
+                                                    })
+                                                },
+                                            ),
+                                        )
+                                        .await
+                                        .map(|output| {
+                                            let failures = describe_failures_message(&output);
+                                            (services_from_output(output), failures)
+                                        })
+                                        .map_err(|e| DisplayErrorContext(e).to_string());
+                                    Load::Services {
+                                        key: cache_key,
+                                        result,
+                                    }
+                                });
+                            }
+                        }
                     }
                 }
+                SettingConfig::Favorites => {}
             }
         }
     }
 
-    pub fn toggle_setting(&mut self) {
-        if let Some(config_mode) = &self.setting_config {
-            match config_mode {
-                SettingConfig::Profile => {
-                    self.setting_config = Some(SettingConfig::Cluster);
-                }
-                SettingConfig::Cluster => {
-                    self.setting_config = Some(SettingConfig::Service);
-                }
-                SettingConfig::Service => {
-                    self.setting_config = Some(SettingConfig::Profile);
-                }
-            }
-        } else {
+    fn set_profiles(&mut self, profiles: Vec<(String, Option<String>)>) {
+        self.profile_regions = profiles
+            .iter()
+            .filter_map(|(name, region)| Some((name.clone(), region.clone()?)))
+            .collect();
+        self.profiles =
+            OptionList::from_iter(profiles.into_iter().map(|(name, _)| name).filter(|name| {
+                self.profile_filter
+                    .as_ref()
+                    .is_none_or(|filter| name.contains(filter.as_str()))
+            }));
+        self.profiles_loaded = true;
+        // keep the cursor on the active profile after a reload
+        if let Some(i) = self.profiles.items.iter().position(|p| *p == self.profile) {
+            self.profiles.state.select(Some(i));
+        }
+    }
+
+    // Pre-selects whatever was passed on the command line. With everything set
+    // the app starts tailing straight away; otherwise the popup opens on the
+    // first box still left to pick.
+    pub async fn start_with(&mut self, cli: Cli) {
+        self.profile_filter = cli
+            .profile_filter
+            .or_else(|| std::env::var("LAZYLOGGER_PROFILE_FILTER").ok())
+            .filter(|filter| !filter.is_empty());
+        if cli.demo {
+            self.demo = true;
+            self.set_profiles(demo::profiles());
+            self.current_screen = CurrentScreen::SettingConfig;
             self.setting_config = Some(SettingConfig::Profile);
+            return;
         }
+        let Some(profile) = cli.profile else {
+            self.start_with_env_profile().await;
+            return;
+        };
+        self.profile = profile;
+        if let Ok(profiles) = get_profiles().await {
+            self.set_profiles(profiles);
+        }
+        if let Some(log_group) = cli.log_group {
+            self.direct_log_group = Some(log_group);
+            self.viewing_logs = true;
+            return;
+        }
+        match (cli.cluster, cli.service.is_empty()) {
+            (Some(cluster), false) => {
+                self.cluster = cluster;
+                self.service = cli.service;
+                self.viewing_logs = true;
+            }
+            (Some(cluster), true) => {
+                self.cluster = cluster;
+                self.current_screen = CurrentScreen::SettingConfig;
+                self.setting_config = Some(SettingConfig::Service);
+            }
+            (None, _) => {
+                self.current_screen = CurrentScreen::SettingConfig;
+                self.setting_config = Some(SettingConfig::Cluster);
+            }
+        }
+    }
+
+    // AWS_PROFILE (or the older AWS_DEFAULT_PROFILE) is only honoured when it
+    // names a profile that exists in ~/.aws; otherwise the picker opens as usual.
+    // Its credentials are checked by the identity lookup on the first tick, which
+    // reopens the picker if they don't resolve.
+    // `aws-vault exec` sets neither, only AWS_VAULT alongside the temporary
+    // credentials it exports; the SDK picks those up from the environment
+    // before looking at the profile, so its config-only profile works as is.
+    async fn start_with_env_profile(&mut self) {
+        let Some((var, profile)) = ["AWS_PROFILE", "AWS_DEFAULT_PROFILE", "AWS_VAULT"]
+            .into_iter()
+            .find_map(|var| {
+                std::env::var(var)
+                    .ok()
+                    .filter(|value| !value.is_empty())
+                    .map(|value| (var, value))
+            })
+        else {
+            return;
+        };
+        let profiles = match get_profiles().await {
+            Ok(profiles) => profiles,
+            Err(e) => {
+                self.picker_error = Some(e.to_string());
+                return;
+            }
+        };
+        let known = profiles.iter().any(|(name, _)| *name == profile);
+        self.current_screen = CurrentScreen::SettingConfig;
+        if known {
+            self.profile = profile;
+            self.set_profiles(profiles);
+            self.setting_config = Some(SettingConfig::Cluster);
+            self.env_profile_var = Some(var);
+        } else {
+            self.set_profiles(profiles);
+            self.setting_config = Some(SettingConfig::Profile);
+            self.status_message = Some(format!(
+                "{} is set to {}, which isn't defined in ~/.aws; pick a profile instead",
+                var, profile
+            ));
+        }
+    }
+
+    // on_tick for --demo: everything a tick would fetch comes from the fixtures.
+    fn demo_tick(&mut self) {
+        if !self.profile.is_empty() && self.identity_profile.as_ref() != Some(&self.profile) {
+            self.identity_profile = Some(self.profile.clone());
+            self.identity = Some(CallerIdentity {
+                account: demo::ACCOUNT.to_string(),
+                arn: format!(
+                    "arn:aws:sts::{}:assumed-role/Demo/{}",
+                    demo::ACCOUNT,
+                    self.profile
+                ),
+            });
+        }
+        if let CurrentScreen::SettingConfig = self.current_screen {
+            self.log_stream = None;
+            if self.logs_loaded {
+                self.reset_logs();
+            }
+            match self.setting_config {
+                Some(SettingConfig::Profile) if !self.profiles_loaded => {
+                    self.set_profiles(demo::profiles());
+                }
+                Some(SettingConfig::Cluster)
+                    if !self.profile.is_empty() && self.clusters.items.is_empty() =>
+                {
+                    self.set_clusters(&demo::clusters());
+                }
+                Some(SettingConfig::Service)
+                    if !self.cluster.is_empty() && self.services.items.is_empty() =>
+                {
+                    let services = demo::services(&self.cluster);
+                    self.service_cache.insert(
+                        (self.profile.clone(), self.cluster.clone()),
+                        services.clone(),
+                    );
+                    self.services_detail = services;
+                    self.rebuild_services();
+                }
+                _ => {}
+            }
+        }
+        if let CurrentScreen::Main = self.current_screen
+            && self.has_log_target()
+            && !self.logs_loaded
+        {
+            let merged = self.service.len() > 1;
+            let now = Local::now().timestamp_millis();
+            let mut events = Vec::new();
+            let mut sources = Vec::new();
+            for name in &self.service {
+                let page = LogPage {
+                    events: demo::log_events(name, now),
+                    truncated: false,
+                    next_tokens: vec![None],
+                };
+                let source = LogSource {
+                    service: name.clone(),
+                    log_groups: vec![(String::new(), format!("/ecs/{}", name))],
+                    next_tokens: vec![None],
+                    container: None,
+                    containers: Vec::new(),
+                    truncated: false,
+                    info: None,
+                };
+                events.extend(page_events(&page, &source, merged));
+                sources.push(source);
+            }
+            if merged {
+                events.sort_by_key(|event| event.timestamp);
+            }
+            self.source_tags = sources.iter().flat_map(|s| s.tags(merged)).collect();
+            self.log_sources = sources;
+            self.logs_loaded = true;
+            self.last_refreshed = Some(Local::now());
+            self.service_events = OptionList::from_iter(events);
+            self.service_events
+                .state
+                .select(self.service_events.items.len().checked_sub(1));
+            self.refilter();
+        }
+        // the other screens have nothing to show, but shouldn't sit on "Loading..."
+        self.deployments_refreshed.get_or_insert_with(Instant::now);
+        self.stopped_tasks_refreshed
+            .get_or_insert_with(Instant::now);
+        self.log_streams_refreshed.get_or_insert_with(Instant::now);
+        self.ecs_events_refreshed.get_or_insert_with(Instant::now);
+    }
+
+    // Loads the first page of logs for every selected service. With more than one
+    // service the lines are tagged with their source and merged by timestamp.
+    async fn fetch_logs(&mut self) -> Result<(Vec<LogSource>, Vec<DisplayEvent>), String> {
+        let aws_config = self.sdk_config().await;
+        let ecs_client = aws_sdk_ecs::Client::new(&aws_config);
+        let cw_client = aws_sdk_cloudwatchlogs::Client::new(&aws_config);
+        if let Some((log_group, log_stream)) = self.log_stream.clone() {
+            let events = match self
+                .api_log
+                .record(
+                    "GetLogEvents",
+                    get_stream_events(&cw_client, &log_group, &log_stream),
+                )
+                .await
+            {
+                Ok(events) => events,
+                Err(e) => return Err(self.aws_error_message(&e)),
+            };
+            let page = LogPage {
+                events,
+                truncated: false,
+                next_tokens: vec![None],
+            };
+            let source = LogSource {
+                service: log_stream,
+                log_groups: vec![(String::new(), log_group)],
+                next_tokens: page.next_tokens.clone(),
+                container: None,
+                containers: Vec::new(),
+                truncated: false,
+                info: None,
+            };
+            let events = page_events(&page, &source, false);
+            return Ok((vec![source], events));
+        }
+        if let Some(log_group) = self.direct_log_group.clone() {
+            let page = match self
+                .api_log
+                .record(
+                    "FilterLogEvents",
+                    get_logs(
+                        &cw_client,
+                        std::slice::from_ref(&log_group),
+                        self.stream_prefix.as_deref(),
+                        self.filter_pattern.as_deref(),
+                        self.time_window,
+                        &[],
+                    ),
+                )
+                .await
+            {
+                Ok(page) => page,
+                Err(e) => return Err(self.aws_error_message(&e)),
+            };
+            let source = LogSource {
+                service: log_group.clone(),
+                log_groups: vec![(String::new(), log_group)],
+                next_tokens: page.next_tokens.clone(),
+                container: None,
+                containers: Vec::new(),
+                truncated: page.truncated,
+                info: None,
+            };
+            let events = page_events(&page, &source, false);
+            return Ok((vec![source], events));
+        }
+        let merged = self.service.len() > 1;
+        let mut cluster_services: Option<Vec<Service>> = None;
+        let mut sources = Vec::new();
+        let mut events = Vec::new();
+        let mut failures = Vec::new();
+        for name in self.service.clone() {
+            let (source, page) = match self
+                .fetch_service_logs(&ecs_client, &cw_client, &name, &mut cluster_services)
+                .await
+            {
+                Ok(fetched) => fetched,
+                // one broken service shouldn't hide the rest of a merged stream
+                Err(e) if merged => {
+                    failures.push(format!("{}: {}", name, e));
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            events.extend(page_events(&page, &source, merged));
+            sources.push(source);
+        }
+        if sources.is_empty() && !failures.is_empty() {
+            return Err(failures.join("\n"));
+        }
+        if !failures.is_empty() {
+            self.status_message = Some(format!("Skipped {}", failures.join("; ")));
+        }
+        if merged {
+            events.sort_by_key(|event| event.timestamp);
+        }
+        Ok((sources, events))
+    }
+
+    async fn fetch_service_logs(
+        &mut self,
+        ecs_client: &aws_sdk_ecs::Client,
+        cw_client: &aws_sdk_cloudwatchlogs::Client,
+        name: &str,
+        cluster_services: &mut Option<Vec<Service>>,
+    ) -> Result<(LogSource, LogPage), String> {
+        // Reuse the service loaded by the picker instead of describing the whole
+        // cluster again on every fetch.
+        let service_obj = match self.service_detail(name).cloned() {
+            Some(service_obj) => service_obj,
+            None => {
+                if cluster_services.is_none() {
+                    match self
+                        .api_log
+                        .record(
+                            "ListServices + DescribeServices",
+                            get_services(ecs_client, self.cluster_id()),
+                        )
+                        .await
+                    {
+                        Ok(output) => *cluster_services = Some(services_from_output(output)),
+                        Err(e) => return Err(self.aws_error_message(&e)),
+                    }
+                }
+                cluster_services
+                    .iter()
+                    .flatten()
+                    .find(|s| s.service_name().unwrap_or_default() == name)
+                    .cloned()
+                    .ok_or_else(|| format!("Service {} not found", name))?
+            }
+        };
+        // keyed on the ARN the service names, which is also what's looked up
+        // next time, even when an older deployment's revision was described
+        let cache_key = service_obj
+            .task_definition()
+            .map(|arn| (self.profile.clone(), arn.to_string()));
+        let cached = cache_key
+            .as_ref()
+            .and_then(|key| self.task_definition_cache.get(key))
+            .cloned();
+        let resolved = match cached {
+            Some(resolved) => resolved,
+            None => {
+                let task_def = match self
+                    .api_log
+                    .record(
+                        "DescribeTaskDefinition",
+                        get_task_definition(ecs_client, &service_obj),
+                    )
+                    .await
+                {
+                    Ok(task_def) => task_def,
+                    Err(e) => return Err(self.aws_error_message(&e)),
+                };
+                let resolved = ResolvedTaskDefinition {
+                    log_groups: match log_groups_from_task_definition(&task_def) {
+                        Ok(log_groups) => log_groups,
+                        Err(e) => return Err(self.aws_error_message(&e)),
+                    },
+                    info: service_info(&service_obj, &task_def),
+                };
+                if let Some(key) = cache_key {
+                    self.task_definition_cache.insert(key, resolved.clone());
+                }
+                resolved
+            }
+        };
+        let log_groups = resolved.log_groups;
+        // the counts come from the service, not the task definition
+        let info = ServiceInfo {
+            desired_count: service_obj.desired_count(),
+            running_count: service_obj.running_count(),
+            ..resolved.info
+        };
+        // the first container wins unless another (or all of them) was picked with C
+        let override_container = self.container_overrides.get(name);
+        let (container, tailed) = if override_container.is_some_and(|c| c == ALL_CONTAINERS) {
+            let mut tailed: Vec<(String, String)> = Vec::new();
+            for (container, log_group) in &log_groups {
+                // containers sharing a group would only repeat its events
+                if !tailed.iter().any(|(_, group)| group == log_group) {
+                    tailed.push((container.clone(), log_group.clone()));
+                }
+            }
+            (None, tailed)
+        } else {
+            let (container, log_group) = override_container
+                .and_then(|container| log_groups.iter().find(|(c, _)| c == container))
+                .unwrap_or(&log_groups[0])
+                .clone();
+            (Some(container.clone()), vec![(container, log_group)])
+        };
+        let groups: Vec<String> = tailed.iter().map(|(_, group)| group.clone()).collect();
+        match self
+            .api_log
+            .record(
+                "FilterLogEvents",
+                get_logs(
+                    cw_client,
+                    &groups,
+                    self.stream_prefix.as_deref(),
+                    self.filter_pattern.as_deref(),
+                    self.time_window,
+                    &[],
+                ),
+            )
+            .await
+        {
+            Ok(page) => Ok((
+                LogSource {
+                    service: name.to_string(),
+                    log_groups: tailed,
+                    next_tokens: page.next_tokens.clone(),
+                    container,
+                    containers: log_groups.into_iter().map(|(c, _)| c).collect(),
+                    truncated: page.truncated,
+                    info: Some(info),
+                },
+                page,
+            )),
+            Err(e) => Err(self.aws_error_message(&e)),
+        }
+    }
+
+    // Every branch of a tick that fails calls this, but only the first failure
+    // before the backoff starts is counted: a pending retry means this round
+    // was already noted, so one offline tick grows the backoff by one step.
+    fn note_failure(&mut self, message: &str) {
+        if is_network_error(message) && self.retry_at.is_none() {
+            self.network_failures += 1;
+            let backoff = Duration::from_secs(1 << self.network_failures.min(6));
+            self.retry_at = Some(Instant::now() + backoff.min(MAX_RETRY_BACKOFF));
+        }
+    }
+
+    // Expired SSO sessions are the most common failure, so they get an actionable
+    // message instead of the raw SDK error chain.
+    fn aws_error_message(&mut self, e: &AppError) -> String {
+        self.error_message(DisplayErrorContext(e).to_string())
+    }
+
+    fn error_message(&mut self, message: String) -> String {
+        if is_access_denied(&message) {
+            let action = denied_action(&message).unwrap_or("this API");
+            if self
+                .denied_actions
+                .insert(format!("{} {}", self.profile, action))
+            {
+                self.error_popup = Some(format!(
+                    "Profile {} isn't allowed to call {}.\n\nAdd the permission to the IAM role or user behind the profile, then press ({}) to retry.\n\n{}",
+                    self.profile,
+                    action,
+                    key_name(self.keymap.refresh),
+                    message
+                ));
+            }
+        }
+        if is_sso_expired(&message) {
+            self.sso_login_needed = true;
+            format!(
+                "The SSO session for profile {} has expired. Press (L) to run `aws sso login --profile {}`, or run it yourself and press ({}) to retry.",
+                self.profile,
+                self.profile,
+                key_name(self.keymap.refresh)
+            )
+        } else {
+            message
+        }
+    }
+
+    // Hands the terminal over to the AWS CLI so the browser login flow can run,
+    // then clears the errors so everything is retried on the next tick.
+    fn sso_login<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
+        disable_raw_mode()?;
+        if self.alt_screen {
+            execute!(io::stdout(), LeaveAlternateScreen)?;
+        }
+        if self.mouse_enabled {
+            execute!(io::stdout(), DisableMouseCapture)?;
+        }
+        let status = Command::new("aws")
+            .args(["sso", "login", "--profile", &self.profile])
+            .status();
+        enable_raw_mode()?;
+        if self.alt_screen {
+            execute!(io::stdout(), EnterAlternateScreen)?;
+        }
+        if self.mouse_enabled {
+            execute!(io::stdout(), EnableMouseCapture)?;
+        }
+        terminal.clear()?;
+
+        self.status_message = Some(match status {
+            Ok(status) if status.success() => "SSO login complete".to_string(),
+            Ok(status) => format!("aws sso login failed ({})", status),
+            Err(e) => format!("Could not run aws sso login: {}", e),
+        });
+        self.sso_login_needed = false;
+        self.identity_profile = None;
+        self.picker_error = None;
+        self.log_error = None;
+        self.deployments_error = None;
+        Ok(())
+    }
+
+    // Picker loads run on their own task so the UI keeps responding. Each load is
+    // tagged with the generation it was issued for; changing the selection bumps
+    // the generation, so a slow result for an earlier pick is never shown.
+    fn spawn_load<F>(&mut self, load: impl FnOnce(LoadSender) -> F)
+    where
+        F: Future<Output = Load> + Send + 'static,
+    {
+        self.cancel_loads();
+        let sender = LoadSender {
+            generation: self.load_generation,
+            tx: self.load_tx.clone(),
+        };
+        let load = load(sender.clone());
+        self.load_task = Some(tokio::spawn(async move {
+            sender.send(load.await);
+        }));
+    }
+
+    pub fn load_pending(&self) -> bool {
+        self.load_task
+            .as_ref()
+            .is_some_and(|task| !task.is_finished())
+    }
+
+    pub fn cancel_loads(&mut self) {
+        self.load_generation += 1;
+        self.service_load_progress = None;
+        if let Some(task) = self.load_task.take() {
+            task.abort();
+        }
+    }
+
+    fn receive_loads(&mut self) {
+        while let Ok((generation, load)) = self.load_rx.try_recv() {
+            // identity lookups aren't cancelled along with the picker loads, so
+            // they're matched on profile instead; a failed one is looked up again
+            // on the next retry or Ctrl-R
+            if let Load::Identity { profile, result } = load {
+                self.resolve_identity(&profile, result);
+                continue;
+            }
+            if generation != self.load_generation {
+                continue;
+            }
+            if let Load::ServiceProgress { loaded, total } = load {
+                self.service_load_progress = Some((loaded, total));
+                continue;
+            }
+            self.load_task = None;
+            self.service_load_progress = None;
+            match load {
+                Load::Clusters { profile, result } => match result {
+                    Ok(clusters) => {
+                        self.network_failures = 0;
+                        self.set_clusters(&clusters);
+                        self.cluster_cache.insert(profile, clusters);
+                    }
+                    Err(e) => {
+                        let message = self.error_message(e);
+                        self.note_failure(&message);
+                        self.picker_error = Some(message);
+                    }
+                },
+                Load::Services { key, result } => match result {
+                    Ok((services, failures)) => {
+                        self.network_failures = 0;
+                        if failures.is_some() {
+                            self.status_message = failures;
+                        }
+                        self.service_cache.insert(key, services.clone());
+                        self.services_detail = services;
+                        self.rebuild_services();
+                    }
+                    Err(e) => {
+                        let message = self.error_message(e);
+                        self.note_failure(&message);
+                        self.picker_error = Some(message);
+                    }
+                },
+                Load::ServiceProgress { .. } | Load::Identity { .. } => {}
+            }
+        }
+    }
+
+    fn resolve_identity(&mut self, profile: &str, result: Result<CallerIdentity, String>) {
+        // only the first lookup for a profile taken from the environment decides
+        // whether its cluster list was the right place to start
+        let env_profile_var = self.env_profile_var.take();
+        if profile != self.profile {
+            return;
+        }
+        match result {
+            Ok(identity) => self.identity = Some(identity),
+            Err(e) => {
+                let Some(var) = env_profile_var else {
+                    return;
+                };
+                let message = self.error_message(e);
+                if let Some(SettingConfig::Cluster) = self.setting_config {
+                    self.cancel_loads();
+                    self.picker_error = None;
+                    self.setting_config = Some(SettingConfig::Profile);
+                }
+                self.status_message = Some(format!(
+                    "{} is set to {}, but its credentials didn't resolve: {}",
+                    var, self.profile, message
+                ));
+            }
+        }
+    }
+
+    fn set_clusters(&mut self, clusters: &[Cluster]) {
+        self.cluster_arns = clusters
+            .iter()
+            .filter_map(|c| Some((c.cluster_name()?.to_string(), c.cluster_arn()?.to_string())))
+            .collect();
+        self.clusters = OptionList::from_iter(
+            clusters
+                .iter()
+                .filter_map(|c| Some(c.cluster_name()?.to_string())),
+        );
+    }
+
+    pub fn rebuild_services(&mut self) {
+        let selected = self.services.selected().cloned();
+        let mut services: Vec<&Service> = self
+            .services_detail
+            .iter()
+            .filter(|s| self.launch_type_filter.matches(s))
+            .collect();
+        if self.sort_services_by_health {
+            // stable sort, so degraded services keep their alphabetical order
+            services.sort_by_key(|s| !service_is_degraded(s));
+        }
+        // a service ECS describes without a name can't be picked anyway
+        self.services = OptionList::from_iter(
+            services
+                .iter()
+                .filter_map(|s| s.service_name())
+                .map(String::from)
+                .collect::<Vec<String>>(),
+        );
+        if let Some(selected) = selected {
+            let position = self.services.items.iter().position(|s| *s == selected);
+            if position.is_some() {
+                self.services.state.select(position);
+            }
+        }
+    }
+
+    // AWS_REGION (or AWS_DEFAULT_REGION) wins, as it does for the AWS CLI; then
+    // a `region` in the profile's ~/.aws/config section, then the default region.
+    pub fn region(&self) -> &str {
+        self.env_region
+            .as_deref()
+            .or_else(|| self.profile_regions.get(&self.profile).map(String::as_str))
+            .unwrap_or(DEFAULT_REGION)
+    }
+
+    async fn sdk_config(&self) -> SdkConfig {
+        aws_config::defaults(BehaviorVersion::latest())
+            .region(Region::new(self.region().to_string()))
+            .profile_name(&self.profile)
+            .load()
+            .await
+    }
+
+    // Prefer the ARN for AWS calls so identically named clusters can't be confused;
+    // the friendly name is what gets displayed.
+    pub fn cluster_id(&self) -> &str {
+        self.cluster_arns
+            .get(&self.cluster)
+            .map(String::as_str)
+            .unwrap_or(&self.cluster)
+    }
+
+    pub fn services_loaded(&self) -> bool {
+        self.service_cache
+            .contains_key(&(self.profile.clone(), self.cluster.clone()))
+    }
+
+    pub fn service_detail(&self, name: &str) -> Option<&Service> {
+        self.services_detail
+            .iter()
+            .find(|s| s.service_name() == Some(name))
+    }
+
+    // A --log-group on the command line stands in for the whole
+    // profile/cluster/service selection.
+    pub fn has_log_target(&self) -> bool {
+        !self.profile.is_empty()
+            && (self.direct_log_group.is_some()
+                || (!self.cluster.is_empty() && !self.service.is_empty()))
+    }
+
+    pub fn format_timestamp(&self, millis: i64, now: DateTime<Utc>) -> String {
+        self.timestamp_mode
+            .format(millis, now, self.timestamp_format.as_deref())
+    }
+
+    // "profile / cluster / services" (or the log group in direct mode) for the header.
+    pub fn breadcrumb(&self) -> Option<String> {
+        if self.profile.is_empty() {
+            return None;
+        }
+        let mut parts = vec![self.profile.clone()];
+        if let Some(log_group) = &self.direct_log_group {
+            parts.push(log_group.clone());
+        } else if !self.cluster.is_empty() {
+            parts.push(self.cluster.clone());
+            if !self.service.is_empty() {
+                parts.push(self.service.join(", "));
+            }
+        }
+        Some(parts.join(" / "))
+    }
+
+    // Called as the user scrolls down, so a viewer parked at the bottom doesn't
+    // page through the whole window on their own.
+    pub fn prefetch_if_near_end(&mut self) {
+        let near_end = self
+            .service_events
+            .state
+            .selected()
+            .is_some_and(|i| i + PREFETCH_MARGIN >= self.service_events.items.len());
+        if near_end && self.logs_truncated && self.prefetch_remaining == 0 {
+            self.prefetch_remaining = self.prefetch_pages;
+        }
+    }
+
+    pub fn log_fetch_state(&self) -> LogFetchState {
+        if !self.has_log_target() {
+            LogFetchState::Idle
+        } else if self.load_more_logs || (!self.logs_loaded && self.log_error.is_none()) {
+            LogFetchState::Fetching
+        } else if self.service_events.items.is_empty() {
+            LogFetchState::Empty
+        } else {
+            LogFetchState::Loaded
+        }
+    }
+
+    pub fn is_prod(&self) -> bool {
+        !self.profile.is_empty()
+            && (self.prod_profiles.contains(&self.profile)
+                || self.identity_profile.as_ref() == Some(&self.profile)
+                    && self
+                        .identity
+                        .as_ref()
+                        .is_some_and(|identity| self.prod_profiles.contains(&identity.account)))
+    }
+
+    // Unfocusing remembers the selection and scroll so refocusing lands in the
+    // same place, even if the list was scrolled with the mouse in between.
+    pub fn toggle_focus(&mut self) {
+        if self.viewing_logs {
+            self.event_box.saved_position = Some(EventPosition {
+                events: self.service_events.state.clone(),
+                wrapped: self.wrap_state.clone(),
+                horizontal_scroll: self.event_box.horizontal_scroll,
+            });
+        } else if let Some(position) = self.event_box.saved_position.take() {
+            self.service_events.state = position.events;
+            self.wrap_state = position.wrapped;
+            self.event_box.horizontal_scroll = position.horizontal_scroll;
+        }
+        self.viewing_logs = !self.viewing_logs;
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.network_failures >= OFFLINE_AFTER_FAILURES
+    }
+
+    pub fn retry_in(&self) -> Duration {
+        self.retry_at
+            .map(|retry_at| retry_at.saturating_duration_since(Instant::now()))
+            .unwrap_or_default()
+    }
+
+    // ECS returns service events newest first; they're listed like log lines so
+    // the timestamp modes and merged-service tags apply to both panes.
+    fn set_ecs_events(&mut self, services: &[Service]) {
+        let merged = services.len() > 1;
+        let mut events: Vec<DisplayEvent> = services
+            .iter()
+            .flat_map(|service| {
+                let tag = merged.then(|| service.service_name().unwrap_or_default().to_string());
+                service.events().iter().map(move |event| DisplayEvent {
+                    timestamp: event
+                        .created_at()
+                        .and_then(|created_at| created_at.to_millis().ok())
+                        .unwrap_or_default(),
+                    tag: tag.clone(),
+                    message: event.message().unwrap_or_default().to_string(),
+                    ..DisplayEvent::default()
+                })
+            })
+            .collect();
+        events.sort_by_key(|event| event.timestamp);
+        // new events arrive at the end, so an existing selection stays put
+        let selected = self.ecs_events.state.selected();
+        self.ecs_events = OptionList::from_iter(events);
+        let last = self.ecs_events.items.len().checked_sub(1);
+        self.ecs_events
+            .state
+            .select(selected.zip(last).map(|(i, last)| i.min(last)).or(last));
+    }
+
+    pub fn next_ecs_event(&mut self) {
+        self.ecs_events.next();
+        self.note_ecs_scroll();
+    }
+
+    pub fn previous_ecs_event(&mut self) {
+        self.ecs_events.previous();
+        self.note_ecs_scroll();
+    }
+
+    // Reading back through the events pauses their refresh so they don't move
+    // underneath; getting back to the newest one resumes it straight away.
+    fn note_ecs_scroll(&mut self) {
+        let at_bottom = self
+            .ecs_events
+            .state
+            .selected()
+            .is_none_or(|i| i + 1 >= self.ecs_events.items.len());
+        self.last_user_scroll = (!at_bottom).then(Instant::now);
+    }
+
+    pub fn ecs_refresh_paused(&self) -> bool {
+        self.last_user_scroll
+            .is_some_and(|scrolled| scrolled.elapsed() < SCROLL_PAUSE)
+    }
+
+    // Opens the stream list for the selected source's log group.
+    pub fn open_log_streams(&mut self) {
+        let Some(log_group) = self
+            .selected_log_source()
+            .and_then(|source| source.log_groups.first())
+            .map(|(_, group)| group.clone())
+        else {
+            return;
+        };
+        self.log_streams = OptionList::new();
+        self.log_streams_error = None;
+        self.log_streams_refreshed = None;
+        self.log_streams_group = Some(log_group);
+        self.current_screen = CurrentScreen::LogStreams;
+    }
+
+    // None goes back to reading every stream of the selection.
+    pub fn view_log_stream(&mut self, log_stream: Option<String>) {
+        self.log_stream = log_stream
+            .zip(self.log_streams_group.clone())
+            .map(|(stream, group)| (group, stream));
+        self.current_screen = CurrentScreen::Main;
+        self.reset_logs();
+    }
+
+    // Coming back to the logs reloads them, since they don't refresh while hidden.
+    pub fn toggle_event_source(&mut self) {
+        self.event_source = match self.event_source {
+            EventSource::Logs => EventSource::EcsEvents,
+            EventSource::EcsEvents => {
+                self.reset_logs();
+                EventSource::Logs
+            }
+        };
+        self.ecs_events_refreshed = None;
+        self.last_user_scroll = None;
+    }
+
+    pub fn ecs_events_shown(&self) -> bool {
+        self.split_view || matches!(self.event_source, EventSource::EcsEvents)
+    }
+
+    // Whether the arrow keys move through the ECS events rather than the logs.
+    pub fn ecs_events_active(&self) -> bool {
+        matches!(self.event_source, EventSource::EcsEvents)
+            || (self.split_view && self.ecs_events_focused)
+    }
+
+    pub fn toggle_split_view(&mut self) {
+        self.split_view = !self.split_view;
+        self.ecs_events_focused = false;
+        self.ecs_events_refreshed = None;
+        self.last_user_scroll = None;
+    }
+
+    pub fn reset_logs(&mut self) {
+        // a line picked above the bottom is found again after the reload if it's
+        // still there; otherwise the reload follows the newest line
+        let selected = self.service_events.state.selected();
+        self.reselect_line = selected
+            .filter(|&i| i + 1 < self.service_events.items.len())
+            .and_then(|i| self.service_events.items.get(i).cloned());
+        self.service_events = OptionList::new();
+        self.ecs_events = OptionList::new();
+        self.ecs_events_error = None;
+        self.ecs_events_refreshed = None;
+        self.event_box.saved_position = None;
+        self.prefetch_remaining = 0;
+        self.logs_loaded = false;
+        self.log_error = None;
+        self.refilter();
+    }
+
+    pub fn search_active(&self) -> bool {
+        !self.search_query.is_empty()
+    }
+
+    // Called whenever the query or the regex toggle changes, so the pattern is
+    // only compiled once per edit rather than once per line. Plain searches are
+    // compiled too (escaped, case-insensitive) so both modes share match ranges.
+    pub fn update_search(&mut self) {
+        self.search_regex = None;
+        self.search_error = None;
+        if self.search_active() {
+            let pattern = if self.use_regex {
+                self.search_query.clone()
+            } else {
+                regex::escape(&self.search_query)
+            };
+            match RegexBuilder::new(&pattern)
+                .case_insensitive(!self.use_regex)
+                .build()
+            {
+                Ok(regex) => self.search_regex = Some(regex),
+                Err(e) => {
+                    self.search_error = e.to_string().lines().last().map(str::to_string);
+                }
+            }
+        }
+        self.refilter();
+    }
+
+    pub fn matches_search(&self, event: &DisplayEvent) -> bool {
+        // An invalid pattern leaves the list unfiltered while the error is shown.
+        self.search_regex
+            .as_ref()
+            .is_none_or(|regex| regex.is_match(&event.text()))
+    }
+
+    pub fn search_match_ranges(&self, line: &str) -> Vec<(usize, usize)> {
+        self.search_regex
+            .as_ref()
+            .map(|regex| {
+                regex
+                    .find_iter(line)
+                    .filter(|m| !m.is_empty())
+                    .map(|m| (m.start(), m.end()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn refilter(&mut self) {
+        self.filtered_indices = self
+            .service_events
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, event)| self.matches_search(event) && !self.is_hidden_health_check(event))
+            .map(|(i, _)| i)
+            .collect();
+        let selected = self.service_events.state.selected();
+        if self.filter_active() && !selected.is_some_and(|i| self.filtered_indices.contains(&i)) {
+            self.service_events
+                .state
+                .select(self.filtered_indices.last().copied());
+        }
+    }
+
+    // The list is narrowed by a search, by hiding health checks, or both.
+    pub fn filter_active(&self) -> bool {
+        self.search_active() || self.hide_health_checks
+    }
+
+    fn is_hidden_health_check(&self, event: &DisplayEvent) -> bool {
+        self.hide_health_checks && self.health_checks.is_match(&event.message)
+    }
+
+    pub fn toggle_health_checks(&mut self) {
+        self.hide_health_checks = !self.hide_health_checks;
+        self.refilter();
+        self.status_message = Some(if self.hide_health_checks {
+            let hidden = self
+                .service_events
+                .items
+                .iter()
+                .filter(|event| self.health_checks.is_match(&event.message))
+                .count();
+            format!("Hiding health checks ({} lines)", hidden)
+        } else {
+            "Showing health checks".to_string()
+        });
+    }
+
+    pub fn visible_event_indices(&self) -> Vec<usize> {
+        if self.filter_active() {
+            self.filtered_indices.clone()
+        } else {
+            (0..self.service_events.items.len()).collect()
+        }
+    }
+
+    // The lines `Y` copies: whatever the list shows, search filter and timestamp
+    // format included.
+    pub fn visible_text(&self) -> String {
+        let now = Utc::now();
+        let indices = self.visible_event_indices();
+        let columns = self.column_messages(&indices);
+        indices
+            .into_iter()
+            .zip(columns)
+            .map(|(i, column_message)| {
+                let event = &self.service_events.items[i];
+                self.display_format.render(
+                    &self.format_timestamp(event.timestamp, now),
+                    &column_message.unwrap_or_else(|| event.text()),
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn next_event(&mut self) {
+        // wrapped events span several rows, so move a visual row at a time
+        if self.wrap_logs && !self.wrapped_rows.is_empty() {
+            let row = self.wrap_state.selected().map_or(0, |row| {
+                row.saturating_add(1).min(self.wrapped_rows.len() - 1)
+            });
+            self.select_wrapped_row(row);
+            return;
+        }
+        if !self.filter_active() {
+            self.service_events.next();
+            return;
+        }
+        let selected = self.service_events.state.selected();
+        if let Some(i) = self
+            .filtered_indices
+            .iter()
+            .copied()
+            .find(|&i| selected.is_none_or(|s| i > s))
+        {
+            self.service_events.state.select(Some(i));
+        }
+    }
+
+    pub fn previous_event(&mut self) {
+        if self.wrap_logs && !self.wrapped_rows.is_empty() {
+            let row = self
+                .wrap_state
+                .selected()
+                .map_or(0, |row| row.saturating_sub(1));
+            self.select_wrapped_row(row);
+            return;
+        }
+        if !self.filter_active() {
+            self.service_events.previous();
+            return;
+        }
+        let selected = self.service_events.state.selected();
+        if let Some(i) = self
+            .filtered_indices
+            .iter()
+            .copied()
+            .rev()
+            .find(|&i| selected.is_none_or(|s| i < s))
+        {
+            self.service_events.state.select(Some(i));
+        }
+    }
+
+    // The selected line split into its timestamp, source tag (merged streams
+    // only) and the raw message.
+    pub fn selected_event(&self) -> Option<(i64, Option<&str>, &str)> {
+        let event = self.service_events.selected()?;
+        Some((event.timestamp, event.tag.as_deref(), &event.message))
+    }
+
+    pub fn columns_shown(&self) -> bool {
+        self.show_columns && !self.json_columns.is_empty()
+    }
+
+    // For each of the given lines, its message as drawn with columns on: the
+    // configured JSON fields, padded so they line up across the lines. None
+    // for lines that aren't a JSON object, which are drawn as they are.
+    pub fn column_messages(&self, indices: &[usize]) -> Vec<Option<String>> {
+        if !self.columns_shown() {
+            return vec![None; indices.len()];
+        }
+        let rows: Vec<Option<(Option<&str>, Vec<String>)>> = indices
+            .iter()
+            .map(|&i| {
+                let event = &self.service_events.items[i];
+                let cells = json_cells(&event.message, &self.json_columns)?;
+                Some((event.tag.as_deref(), cells))
+            })
+            .collect();
+        let mut widths = vec![0; self.json_columns.len()];
+        for (_, cells) in rows.iter().flatten() {
+            for (width, cell) in widths.iter_mut().zip(cells) {
+                *width = (*width).max(cell.chars().count()).min(COLUMN_MAX_WIDTH);
+            }
+        }
+        rows.into_iter()
+            .map(|row| {
+                let (tag, cells) = row?;
+                let mut text = tag.map(|tag| format!("[{}] ", tag)).unwrap_or_default();
+                for (i, (cell, width)) in cells.iter().zip(&widths).enumerate() {
+                    if i + 1 == cells.len() {
+                        text.push_str(cell);
+                    } else {
+                        text.push_str(&format!("{:<width$}  ", cell, width = width));
+                    }
+                }
+                Some(text.trim_end().to_string())
+            })
+            .collect()
+    }
+
+    pub fn copy_to_clipboard(&mut self, text: String) {
+        let result = match &mut self.clipboard {
+            Some(clipboard) => clipboard.set_text(text),
+            None => Clipboard::new().and_then(|mut clipboard| {
+                let result = clipboard.set_text(text);
+                self.clipboard = Some(clipboard);
+                result
+            }),
+        };
+        self.status_message = Some(match result {
+            Ok(()) => "Copied to clipboard".to_string(),
+            Err(e) => format!("Could not copy: {}", e),
+        });
+    }
+
+    // The log source of the selected line; merged streams are matched on the
+    // service tag each line carries.
+    pub fn selected_log_source(&self) -> Option<&LogSource> {
+        if self.log_sources.len() < 2 {
+            return self.log_sources.first();
+        }
+        let tag = self.service_events.selected()?.tag.as_deref()?;
+        // merged tags may carry a "/container" suffix
+        let service = tag.split('/').next()?;
+        self.log_sources
+            .iter()
+            .find(|source| source.service == service)
+    }
+
+    // Switches the selected service to its next container with a log group and
+    // remembers the choice in the config file.
+    pub fn cycle_container(&mut self) {
+        let Some(source) = self.selected_log_source() else {
+            return;
+        };
+        if source.containers.len() < 2 {
+            self.status_message =
+                Some(format!("{} has only one container logging", source.service));
+            return;
+        }
+        // each container in turn, then all of them merged
+        let current = match &source.container {
+            Some(container) => source.containers.iter().position(|c| c == container),
+            None => Some(source.containers.len()),
+        };
+        let next = current.map_or(0, |i| (i + 1) % (source.containers.len() + 1));
+        let service = source.service.clone();
+        let container = source
+            .containers
+            .get(next)
+            .cloned()
+            .unwrap_or_else(|| ALL_CONTAINERS.to_string());
+        let label = if container == ALL_CONTAINERS {
+            "all containers".to_string()
+        } else {
+            format!("container {}", container)
+        };
+        self.container_overrides.insert(service.clone(), container);
+        self.status_message = Some(match save_container_overrides(&self.container_overrides) {
+            Ok(()) => format!("Tailing {} for {}", label, service),
+            Err(e) => format!("Could not save container choice: {}", e),
+        });
+        self.reset_logs();
+    }
+
+    pub fn open_in_console(&mut self) {
+        let Some(source) = self.selected_log_source() else {
+            return;
+        };
+        let Some((_, log_group)) = source.log_groups.first() else {
+            return;
+        };
+        let url = log_group_console_url(self.region(), log_group);
+        self.status_message = Some(match open::that_detached(&url) {
+            Ok(()) => format!("Opened {} in the browser", log_group),
+            Err(e) => format!("Could not open browser: {}", e),
+        });
+    }
+
+    pub fn current_bookmark(&self) -> Option<Bookmark> {
+        match self.service.as_slice() {
+            [service] => Some(Bookmark {
+                profile: self.profile.clone(),
+                cluster: self.cluster.clone(),
+                service: service.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn is_bookmarked(&self, profile: &str, cluster: &str, service: &str) -> bool {
+        self.bookmarks
+            .iter()
+            .any(|b| b.profile == profile && b.cluster == cluster && b.service == service)
+    }
+
+    pub fn toggle_bookmark(&mut self) {
+        let Some(bookmark) = self.current_bookmark() else {
+            self.status_message = Some("Only a single service can be bookmarked".to_string());
+            return;
+        };
+        let message = if let Some(i) = self.bookmarks.iter().position(|b| *b == bookmark) {
+            self.bookmarks.remove(i);
+            format!("Removed bookmark {}", bookmark.service)
+        } else {
+            let message = format!("Bookmarked {}", bookmark.service);
+            self.bookmarks.push(bookmark);
+            self.bookmarks.sort_by_key(Bookmark::label);
+            message
+        };
+        self.status_message = Some(match save_bookmarks(&self.bookmarks) {
+            Ok(()) => message,
+            Err(e) => format!("Could not save bookmarks: {}", e),
+        });
+        self.rebuild_favorites();
+    }
+
+    pub fn cycle_theme(&mut self) {
+        self.theme_name = self.theme_name.next();
+        self.theme = self.theme_name.theme();
+        self.status_message = Some(match save_theme(self.theme_name.name()) {
+            Ok(()) => format!("Theme: {}", self.theme_name.name()),
+            Err(e) => format!("Could not save theme: {}", e),
+        });
+    }
+
+    pub fn rebuild_favorites(&mut self) {
+        self.favorites = OptionList::from_iter(self.bookmarks.iter().map(Bookmark::label));
+    }
+
+    // Jumps straight to a bookmarked service, dropping anything loaded for the
+    // previous profile/cluster.
+    pub fn open_bookmark(&mut self, bookmark: Bookmark) {
+        self.profile = bookmark.profile;
+        self.cluster = bookmark.cluster;
+        self.service = vec![bookmark.service];
+        self.direct_log_group = None;
+        self.cancel_loads();
+        self.clusters = OptionList::new();
+        self.cluster_arns.clear();
+        self.services = OptionList::new();
+        self.services_detail.clear();
+        self.picker_error = None;
+        self.reset_logs();
+    }
+
+    pub fn select_wrapped_row(&mut self, row: usize) {
+        if let Some(&i) = self.wrapped_rows.get(row) {
+            self.wrap_state.select(Some(row));
+            self.service_events.state.select(Some(i));
+        }
+    }
+
+    // Drops everything cached for the current profile so clusters, services and
+    // their task definitions (and so the log groups) are looked up again.
+    pub fn refresh_all(&mut self) {
+        self.cancel_loads();
+        self.cluster_cache.remove(&self.profile);
+        let profile = self.profile.clone();
+        self.service_cache
+            .retain(|(cached_profile, _), _| *cached_profile != profile);
+        self.task_definition_cache
+            .retain(|(cached_profile, _), _| *cached_profile != profile);
+        self.clusters = OptionList::new();
+        self.cluster_arns.clear();
+        self.services = OptionList::new();
+        self.services_detail.clear();
+        self.picker_error = None;
+        self.retry_at = None;
+        self.identity_profile = None;
+        self.denied_actions.clear();
+        self.reset_logs();
+        self.status_message = Some(format!("Reloading everything for {}", self.profile));
+    }
+
+    pub fn bust_cache(&mut self) {
+        self.picker_error = None;
+        self.cancel_loads();
+        match self.setting_config {
+            Some(SettingConfig::Cluster) => {
+                self.cluster_cache.remove(&self.profile);
+                self.clusters = OptionList::new();
+            }
+            Some(SettingConfig::Service) => {
+                self.service_cache
+                    .remove(&(self.profile.clone(), self.cluster.clone()));
+                self.services = OptionList::new();
+            }
+            Some(SettingConfig::Profile) => {
+                self.profiles = OptionList::new();
+                self.profiles_loaded = false;
+            }
+            Some(SettingConfig::Favorites) | None => {}
+        }
+    }
+
+    pub fn toggle_setting(&mut self) {
+        if let Some(config_mode) = &self.setting_config {
+            match config_mode {
+                SettingConfig::Profile => {
+                    self.setting_config = Some(SettingConfig::Cluster);
+                }
+                SettingConfig::Cluster => {
+                    self.setting_config = Some(SettingConfig::Service);
+                }
+                SettingConfig::Service if !self.bookmarks.is_empty() => {
+                    self.setting_config = Some(SettingConfig::Favorites);
+                }
+                SettingConfig::Service | SettingConfig::Favorites => {
+                    self.setting_config = Some(SettingConfig::Profile);
+                }
+            }
+        } else {
+            self.setting_config = Some(SettingConfig::Profile);
+        }
+    }
+
+    pub fn toggle_setting_back(&mut self) {
+        if let Some(config_mode) = &self.setting_config {
+            match config_mode {
+                SettingConfig::Profile if !self.bookmarks.is_empty() => {
+                    self.setting_config = Some(SettingConfig::Favorites);
+                }
+                SettingConfig::Profile | SettingConfig::Favorites => {
+                    self.setting_config = Some(SettingConfig::Service);
+                }
+                SettingConfig::Cluster => {
+                    self.setting_config = Some(SettingConfig::Profile);
+                }
+                SettingConfig::Service => {
+                    self.setting_config = Some(SettingConfig::Cluster);
+                }
+            }
+        } else {
+            self.setting_config = Some(SettingConfig::Profile);
+        }
+    }
+
+    // Undoes the last pick: the previous box gets focus again and everything
+    // chosen after it is cleared.
+    pub fn step_back_setting(&mut self) {
+        match self.setting_config {
+            Some(SettingConfig::Service) => {
+                self.setting_config = Some(SettingConfig::Cluster);
+                self.cluster.clear();
+                self.services = OptionList::new();
+            }
+            Some(SettingConfig::Cluster) => {
+                self.setting_config = Some(SettingConfig::Profile);
+                self.profile.clear();
+                self.clusters = OptionList::new();
+                self.cluster.clear();
+                self.services = OptionList::new();
+            }
+            Some(SettingConfig::Favorites) => {
+                self.setting_config = Some(SettingConfig::Service);
+                return;
+            }
+            Some(SettingConfig::Profile) | None => return,
+        }
+        self.cancel_loads();
+        self.picker_error = None;
+        self.service.clear();
+        self.marked_services.clear();
+        self.reset_logs();
+    }
+}
+
+// The page's events for the list, tagged by source and with ANSI codes stripped.
+fn page_events(page: &LogPage, source: &LogSource, merged: bool) -> Vec<DisplayEvent> {
+    page.events
+        .iter()
+        .map(|event| {
+            let (message, colors) = parse_ansi(&event.message);
+            DisplayEvent {
+                timestamp: event.timestamp,
+                tag: source.tag(event, merged),
+                message,
+                colors,
+                is_stderr: event.is_stderr(),
+            }
+        })
+        .collect()
+}
+
+// The values of `columns` in a JSON object line, with dotted names reaching
+// into nested objects. Strings lose their quotes and missing fields are blank.
+fn json_cells(message: &str, columns: &[String]) -> Option<Vec<String>> {
+    let value: serde_json::Value = serde_json::from_str(message.trim()).ok()?;
+    if !value.is_object() {
+        return None;
+    }
+    let cells = columns
+        .iter()
+        .map(|column| {
+            match column
+                .split('.')
+                .try_fold(&value, |value, key| value.get(key))
+            {
+                Some(serde_json::Value::String(text)) => text.clone(),
+                Some(serde_json::Value::Null) | None => String::new(),
+                Some(other) => other.to_string(),
+            }
+        })
+        .collect();
+    Some(cells)
+}
+
+// Counts events per bucket across the loaded span, one bucket per minute
+// unless that needs more than `width` buckets, in which case each covers as
+// many minutes as it takes to fit. Returns the counts and minutes per bucket.
+pub fn event_rate(timestamps: &[i64], width: usize) -> (Vec<u64>, i64) {
+    const MINUTE: i64 = 60_000;
+    let (Some(&first), Some(&last)) = (timestamps.iter().min(), timestamps.iter().max()) else {
+        return (Vec::new(), 1);
+    };
+    let minutes = (last - first) / MINUTE + 1;
+    let per_bucket = (minutes + width.max(1) as i64 - 1) / width.max(1) as i64;
+    let mut buckets = vec![0; ((minutes + per_bucket - 1) / per_bucket) as usize];
+    for &timestamp in timestamps {
+        buckets[((timestamp - first) / MINUTE / per_bucket) as usize] += 1;
+    }
+    (buckets, per_bucket)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log_lines(messages: &[&str]) -> OptionList<DisplayEvent> {
+        OptionList::from_iter(messages.iter().map(|message| DisplayEvent {
+            message: message.to_string(),
+            ..DisplayEvent::default()
+        }))
+    }
+
+    #[test]
+    fn json_cells_follow_dotted_names_and_skip_other_lines() {
+        let columns = ["level", "msg", "http.status"].map(String::from);
+        assert_eq!(
+            json_cells(
+                r#"{"level":"info","msg":"started","http":{"status":200}}"#,
+                &columns
+            ),
+            Some(vec![
+                "info".to_string(),
+                "started".to_string(),
+                "200".to_string()
+            ])
+        );
+        assert_eq!(
+            json_cells(r#"{"level":"warning","msg":null}"#, &columns),
+            Some(vec!["warning".to_string(), String::new(), String::new()])
+        );
+        assert_eq!(json_cells("plain text", &columns), None);
+        assert_eq!(json_cells("[1, 2]", &columns), None);
+    }
+
+    #[test]
+    fn column_messages_are_padded_to_line_up() {
+        let mut app = App::new();
+        app.json_columns = ["level", "msg"].map(String::from).to_vec();
+        app.service_events = log_lines(&[
+            r#"{"level":"info","msg":"started"}"#,
+            r#"{"level":"warning","msg":"slow"}"#,
+            "plain text",
+        ]);
+        assert_eq!(
+            app.column_messages(&[0, 1, 2]),
+            vec![
+                Some("info     started".to_string()),
+                Some("warning  slow".to_string()),
+                None,
+            ]
+        );
+
+        app.show_columns = false;
+        assert_eq!(app.column_messages(&[0]), vec![None]);
+    }
+
+    #[test]
+    fn hidden_health_checks_are_filtered_out() {
+        let mut app = App::new();
+        app.health_checks = regex::RegexSet::new(["/health", "ELB-HealthChecker"]).unwrap();
+        app.service_events = log_lines(&[
+            "GET /health 200",
+            "POST /orders 201",
+            "GET / 200 ELB-HealthChecker/2.0",
+        ]);
+
+        app.toggle_health_checks();
+        assert_eq!(app.visible_event_indices(), vec![1]);
+        assert_eq!(app.service_events.state.selected(), Some(1));
+
+        app.toggle_health_checks();
+        assert_eq!(app.visible_event_indices(), vec![0, 1, 2]);
     }
 }
@@ -1,62 +1,195 @@
+use crate::error::AppError;
+use aws_config::SdkConfig;
 use aws_sdk_cloudwatchlogs as cloudwatch;
 use aws_sdk_ecs::{
+    error::DisplayErrorContext,
     operation::{
         describe_clusters::DescribeClustersOutput, describe_services::DescribeServicesOutput,
     },
-    types::Service,
+    types::{Cluster, DesiredStatus, LaunchType, LogDriver, Service, Task, TaskDefinition},
 };
-use color_eyre::Result;
-use itertools::Itertools;
-use std::{
-    error,
-    fs::File,
-    io::{self, BufRead},
-};
-pub async fn get_profiles() -> Result<Vec<String>, Box<dyn error::Error>> {
-    let mut profiles = Vec::new();
-    let cred_file = File::open(dirs::home_dir().unwrap().join(".aws").join("credentials")).unwrap();
-    let read_creds = io::BufReader::new(cred_file);
-    for line in read_creds.lines() {
-        let line = line.unwrap();
-        if line.starts_with('[') && line.ends_with(']') {
-            let profile = line.trim_matches(&['[', ']'][..]);
-            profiles.push(profile.to_string());
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use futures::{StreamExt, TryStreamExt, future, stream};
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+const DESCRIBE_CONCURRENCY: usize = 8;
+
+// Profiles can be defined in either file: `[name]` in credentials, and
+// `[profile name]` (or `[default]`) in config. Config-only profiles are common
+// for role assumption (`role_arn` + `source_profile`) and SSO, which the SDK's
+// profile provider resolves on its own once the profile name is passed through.
+// A `region` key inside a profile section is returned alongside its name.
+fn parse_profiles(contents: &str, config_file: bool) -> Vec<(String, Option<String>)> {
+    let mut profiles: Vec<(String, Option<String>)> = Vec::new();
+    let mut in_profile = false;
+    for line in contents.lines().map(str::trim) {
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let section = section.trim();
+            let name = if !config_file || section == "default" {
+                Some(section)
+            } else {
+                section.strip_prefix("profile ").map(str::trim)
+            };
+            in_profile = name.is_some();
+            profiles.extend(name.map(|name| (name.to_string(), None)));
+            continue;
+        }
+        let region = line
+            .split_once('=')
+            .filter(|(key, _)| in_profile && key.trim() == "region");
+        if let (Some((_, region)), Some(profile)) = (region, profiles.last_mut()) {
+            profile.1 = Some(region.trim().to_string());
         }
     }
-    profiles.sort();
-    Ok(profiles)
+    profiles
 }
 
-pub async fn get_clusters(
-    client: &aws_sdk_ecs::Client,
-) -> Result<DescribeClustersOutput, aws_sdk_ecs::Error> {
-    let resp = client.list_clusters().send().await?;
-    let mut cluster_arns = resp.cluster_arns().to_vec();
-    cluster_arns.sort();
-    let cluster = client
-        .describe_clusters()
-        .set_clusters(Some(cluster_arns))
+// AWS_SHARED_CREDENTIALS_FILE and AWS_CONFIG_FILE move the files, as they do
+// for the AWS CLI and SDK (which also expand a leading `~`).
+fn aws_file_path(env_var: &str, file_name: &str) -> Result<PathBuf, AppError> {
+    let home = || {
+        dirs::home_dir()
+            .ok_or_else(|| AppError::Credentials("could not determine home directory".to_string()))
+    };
+    match std::env::var(env_var).ok().filter(|path| !path.is_empty()) {
+        Some(path) => match path.strip_prefix("~/") {
+            Some(rest) => Ok(home()?.join(rest)),
+            None => Ok(PathBuf::from(path)),
+        },
+        None => Ok(home()?.join(".aws").join(file_name)),
+    }
+}
+
+pub async fn get_profiles() -> Result<Vec<(String, Option<String>)>, AppError> {
+    // A profile can appear in both files; keep whichever region is set.
+    let mut profiles: BTreeMap<String, Option<String>> = BTreeMap::new();
+    for (env_var, file_name, config_file) in [
+        ("AWS_SHARED_CREDENTIALS_FILE", "credentials", false),
+        ("AWS_CONFIG_FILE", "config", true),
+    ] {
+        match fs::read_to_string(aws_file_path(env_var, file_name)?) {
+            Ok(contents) => {
+                for (name, region) in parse_profiles(&contents, config_file) {
+                    let entry = profiles.entry(name).or_default();
+                    if region.is_some() {
+                        *entry = region;
+                    }
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(profiles.into_iter().collect())
+}
+
+pub struct CallerIdentity {
+    pub account: String,
+    pub arn: String,
+}
+
+// Which account the profile's credentials actually resolve to.
+pub async fn get_caller_identity(config: &SdkConfig) -> Result<CallerIdentity, AppError> {
+    let output = aws_sdk_sts::Client::new(config)
+        .get_caller_identity()
         .send()
-        .await?;
-    Ok(cluster)
+        .await
+        .map_err(aws_sdk_sts::Error::from)?;
+    Ok(CallerIdentity {
+        account: output.account().unwrap_or_default().to_string(),
+        arn: output.arn().unwrap_or_default().to_string(),
+    })
 }
 
-pub async fn get_services(
+// The SSO token provider reports an expired or missing cached token with
+// messages along the lines of "the SSO session associated with this profile
+// has expired"; the exact wording differs between SDK versions.
+pub fn is_sso_expired(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("sso") && message.contains("expired")
+}
+
+// ECS only keeps stopped tasks around for about an hour. A single list_tasks
+// page holds at most 100 ARNs, which is also the describe_tasks limit.
+pub async fn get_stopped_tasks(
     client: &aws_sdk_ecs::Client,
-    cluster_name: &str,
-) -> Result<DescribeServicesOutput, aws_sdk_ecs::Error> {
+    cluster: &str,
+    service_name: &str,
+) -> Result<Vec<Task>, AppError> {
+    let resp = client
+        .list_tasks()
+        .cluster(cluster)
+        .service_name(service_name)
+        .desired_status(DesiredStatus::Stopped)
+        .send()
+        .await
+        .map_err(aws_sdk_ecs::Error::from)?;
+    if resp.task_arns().is_empty() {
+        return Ok(Vec::new());
+    }
+    let output = client
+        .describe_tasks()
+        .cluster(cluster)
+        .set_tasks(Some(resp.task_arns().to_vec()))
+        .send()
+        .await
+        .map_err(aws_sdk_ecs::Error::from)?;
+    let mut tasks = output.tasks().to_vec();
+    // most recent first
+    tasks.sort_by_key(|task| {
+        std::cmp::Reverse(task.stopped_at().and_then(|at| at.to_millis().ok()))
+    });
+    Ok(tasks)
+}
+
+pub fn task_crashed(task: &Task) -> bool {
+    task.containers()
+        .iter()
+        .any(|container| container.exit_code().is_some_and(|code| code != 0))
+}
+
+// Requests that never reached AWS surface as dispatch failures or timeouts
+// rather than service errors.
+pub fn is_network_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("dispatch failure")
+        || message.contains("timeout")
+        || message.contains("timed out")
+}
+
+pub fn is_access_denied(message: &str) -> bool {
+    message.contains("AccessDenied") || message.contains("not authorized to perform")
+}
+
+// IAM denials name the refused action: "... is not authorized to perform:
+// ecs:ListServices on resource: ...".
+pub fn denied_action(message: &str) -> Option<&str> {
+    let (_, rest) = message.split_once("not authorized to perform: ")?;
+    rest.split_whitespace().next()
+}
+
+const DESCRIBE_CLUSTERS_LIMIT: usize = 100;
+const DESCRIBE_SERVICES_LIMIT: usize = 10;
+
+// The describe APIs cap how many ARNs a single call accepts. The chunks are
+// owned so the request futures stay `Send` for spawned loads.
+fn arn_chunks(arns: &[String], size: usize) -> Vec<Vec<String>> {
+    arns.chunks(size).map(<[String]>::to_vec).collect()
+}
+
+pub async fn get_clusters(client: &aws_sdk_ecs::Client) -> Result<Vec<Cluster>, AppError> {
     let mut next_token = None;
-    let mut service_arns: Vec<String> = Vec::new();
+    let mut cluster_arns: Vec<String> = Vec::new();
 
     loop {
         let resp = client
-            .list_services()
-            .cluster(cluster_name)
+            .list_clusters()
             .set_next_token(next_token.clone())
             .send()
-            .await?;
+            .await
+            .map_err(aws_sdk_ecs::Error::from)?;
 
-        service_arns.extend(resp.service_arns().to_vec());
+        cluster_arns.extend(resp.cluster_arns().to_vec());
 
         if let Some(token) = resp.next_token() {
             next_token = Some(token.to_string());
@@ -65,64 +198,783 @@ pub async fn get_services(
         }
     }
 
-    service_arns.sort();
-    let mut all_services: Vec<_> = Vec::new();
+    cluster_arns.sort();
+    let responses: Vec<DescribeClustersOutput> =
+        stream::iter(arn_chunks(&cluster_arns, DESCRIBE_CLUSTERS_LIMIT))
+            .map(|chunk| client.describe_clusters().set_clusters(Some(chunk)).send())
+            .buffered(DESCRIBE_CONCURRENCY)
+            .try_collect()
+            .await
+            .map_err(aws_sdk_ecs::Error::from)?;
+    Ok(responses
+        .into_iter()
+        .flat_map(|output| output.clusters.unwrap_or_default())
+        .collect())
+}
 
-    for chunk in &service_arns.into_iter().chunks(10) {
-        let resp = client
+pub async fn get_services(
+    client: &aws_sdk_ecs::Client,
+    cluster_name: &str,
+) -> Result<DescribeServicesOutput, AppError> {
+    get_services_with_progress(client, cluster_name, |_, _| {}).await
+}
+
+// The two ECS calls get_services is built from, split out so the paging,
+// chunking and merging can be exercised against a fake in tests.
+trait ServiceApi {
+    async fn list_services_page(
+        &self,
+        cluster_name: &str,
+        next_token: Option<String>,
+    ) -> Result<(Vec<String>, Option<String>), AppError>;
+
+    async fn describe_services(
+        &self,
+        cluster_name: &str,
+        arns: Vec<String>,
+    ) -> Result<DescribeServicesOutput, AppError>;
+}
+
+impl ServiceApi for aws_sdk_ecs::Client {
+    async fn list_services_page(
+        &self,
+        cluster_name: &str,
+        next_token: Option<String>,
+    ) -> Result<(Vec<String>, Option<String>), AppError> {
+        let resp = self
+            .list_services()
+            .cluster(cluster_name)
+            .set_next_token(next_token)
+            .send()
+            .await
+            .map_err(aws_sdk_ecs::Error::from)?;
+        Ok((
+            resp.service_arns().to_vec(),
+            resp.next_token().map(String::from),
+        ))
+    }
+
+    async fn describe_services(
+        &self,
+        cluster_name: &str,
+        arns: Vec<String>,
+    ) -> Result<DescribeServicesOutput, AppError> {
+        Ok(self
             .describe_services()
             .cluster(cluster_name)
-            .set_services(Some(chunk.collect()))
+            .set_services(Some(arns))
             .send()
-            .await?;
-        if let Some(s) = resp.services {
-            all_services.extend(s);
+            .await
+            .map_err(aws_sdk_ecs::Error::from)?)
+    }
+}
+
+// Like get_services, calling `progress(described, total)` as each describe
+// chunk comes back so long loads can show how far along they are.
+pub async fn get_services_with_progress(
+    client: &aws_sdk_ecs::Client,
+    cluster_name: &str,
+    progress: impl Fn(usize, usize),
+) -> Result<DescribeServicesOutput, AppError> {
+    collect_services(client, cluster_name, progress).await
+}
+
+async fn collect_services(
+    api: &impl ServiceApi,
+    cluster_name: &str,
+    progress: impl Fn(usize, usize),
+) -> Result<DescribeServicesOutput, AppError> {
+    let mut next_token = None;
+    let mut service_arns: Vec<String> = Vec::new();
+
+    loop {
+        let (arns, token) = api.list_services_page(cluster_name, next_token).await?;
+        service_arns.extend(arns);
+        if token.is_none() {
+            break;
         }
+        next_token = token;
+    }
+
+    service_arns.sort();
+    let total = service_arns.len();
+    progress(0, total);
+
+    // describe_services takes at most 10 services per call; run the chunks
+    // concurrently, but `buffered` keeps them in the sorted order.
+    let mut responses = stream::iter(arn_chunks(&service_arns, DESCRIBE_SERVICES_LIMIT))
+        .map(|chunk| api.describe_services(cluster_name, chunk))
+        .buffered(DESCRIBE_CONCURRENCY);
+    let mut all_services = Vec::new();
+    let mut all_failures = Vec::new();
+    while let Some(resp) = responses.try_next().await? {
+        all_services.extend(resp.services.unwrap_or_default());
+        all_failures.extend(resp.failures.unwrap_or_default());
+        progress(all_services.len() + all_failures.len(), total);
     }
 
     let output = DescribeServicesOutput::builder()
         .set_services(Some(all_services))
+        .set_failures(Some(all_failures))
         .build();
 
     Ok(output)
 }
 
-pub async fn get_log_group_name(
+// ARNs that list_services returned but describe_services couldn't resolve,
+// usually services deleted in between (reason MISSING).
+pub fn describe_failures_message(output: &DescribeServicesOutput) -> Option<String> {
+    let failures = output.failures();
+    if failures.is_empty() {
+        return None;
+    }
+    let details: Vec<String> = failures
+        .iter()
+        .map(|failure| {
+            format!(
+                "{} ({})",
+                failure
+                    .arn()
+                    .and_then(|arn| arn.rsplit('/').next())
+                    .unwrap_or("unknown"),
+                failure.reason().unwrap_or("no reason given")
+            )
+        })
+        .collect();
+    Some(format!(
+        "{} service(s) couldn't be described: {}",
+        failures.len(),
+        details.join(", ")
+    ))
+}
+
+pub fn services_from_output(output: DescribeServicesOutput) -> Vec<Service> {
+    output.services.unwrap_or_default()
+}
+
+pub async fn describe_services_by_name(
+    client: &aws_sdk_ecs::Client,
+    cluster_name: &str,
+    service_names: &[String],
+) -> Result<Vec<Service>, AppError> {
+    let mut services = Vec::new();
+    for chunk in service_names.chunks(DESCRIBE_SERVICES_LIMIT) {
+        let resp = client
+            .describe_services()
+            .cluster(cluster_name)
+            .set_services(Some(chunk.to_vec()))
+            .send()
+            .await
+            .map_err(aws_sdk_ecs::Error::from)?;
+        services.extend(services_from_output(resp));
+    }
+    Ok(services)
+}
+
+// "arn:aws:ecs:eu-west-1:123456789012:cluster/web" -> "eu-west-1"
+pub fn arn_region(arn: &str) -> Option<&str> {
+    arn.split(':').nth(3).filter(|region| !region.is_empty())
+}
+
+// Services on capacity provider strategies have no launch type set, so fall back
+// to the provider names (FARGATE / FARGATE_SPOT) to classify them.
+pub fn service_is_fargate(service: &Service) -> bool {
+    match service.launch_type() {
+        Some(launch_type) => *launch_type == LaunchType::Fargate,
+        None => service
+            .capacity_provider_strategy()
+            .iter()
+            .any(|item| item.capacity_provider().starts_with("FARGATE")),
+    }
+}
+
+pub fn service_is_degraded(service: &Service) -> bool {
+    service.running_count() < service.desired_count()
+}
+
+// The service's task definition revision may have been deregistered and
+// deleted, so fall back to the revisions its deployments are running before
+// giving up.
+pub async fn get_task_definition(
     ecs_client: &aws_sdk_ecs::Client,
     service: &Service,
-) -> Result<String, aws_sdk_ecs::Error> {
-    let task_def_arn = service.task_definition().unwrap();
-    let task_def = ecs_client
-        .describe_task_definition()
-        .task_definition(task_def_arn)
-        .send()
-        .await?;
-    let container_defs = task_def.task_definition().unwrap().container_definitions();
-    let log_config = container_defs[0].log_configuration().unwrap();
-    let log_group = log_config.options().unwrap().get("awslogs-group").unwrap();
-    Ok(log_group.clone())
+) -> Result<TaskDefinition, AppError> {
+    let mut candidates: Vec<&str> = service.task_definition().into_iter().collect();
+    let deployments = service.deployments();
+    for status in ["PRIMARY", "ACTIVE"] {
+        for task_def in deployments
+            .iter()
+            .filter(|d| d.status() == Some(status))
+            .filter_map(|d| d.task_definition())
+        {
+            if !candidates.contains(&task_def) {
+                candidates.push(task_def);
+            }
+        }
+    }
+    // a service that was only just created may not have one registered yet
+    if candidates.is_empty() {
+        return Err(AppError::TaskDefinition(
+            "Service has no task definition yet".to_string(),
+        ));
+    }
+
+    let mut last_error = None;
+    for task_def_arn in candidates {
+        match ecs_client
+            .describe_task_definition()
+            .task_definition(task_def_arn)
+            .send()
+            .await
+        {
+            Ok(output) => {
+                if let Some(task_def) = output.task_definition {
+                    return Ok(task_def);
+                }
+            }
+            Err(e) => last_error = Some(DisplayErrorContext(e).to_string()),
+        }
+    }
+    match last_error {
+        Some(e) => Err(AppError::TaskDefinition(format!(
+            "Task definition unavailable: {}",
+            e
+        ))),
+        None => Err(AppError::TaskDefinition(
+            "Task definition unavailable for this service".to_string(),
+        )),
+    }
+}
+
+// Returns `(container, log group)` for every container that logs to CloudWatch,
+// in task definition order.
+pub fn log_groups_from_task_definition(
+    task_def: &TaskDefinition,
+) -> Result<Vec<(String, String)>, AppError> {
+    let mut log_groups = Vec::new();
+    let mut unsupported_drivers = Vec::new();
+    for container_def in task_def.container_definitions() {
+        let Some(log_config) = container_def.log_configuration() else {
+            continue;
+        };
+        // FireLens routes to CloudWatch through Fluent Bit's cloudwatch output,
+        // which takes the group as `log_group_name` in the log options.
+        let group_option = match log_config.log_driver() {
+            LogDriver::Awslogs => "awslogs-group",
+            LogDriver::Awsfirelens => "log_group_name",
+            other => {
+                unsupported_drivers.push(other.as_str().to_string());
+                continue;
+            }
+        };
+        if let Some(log_group) = log_config.options().and_then(|o| o.get(group_option)) {
+            log_groups.push((
+                container_def.name().unwrap_or_default().to_string(),
+                log_group.clone(),
+            ));
+        }
+    }
+    if !log_groups.is_empty() {
+        Ok(log_groups)
+    } else if unsupported_drivers.is_empty() {
+        Err(AppError::TaskDefinition(
+            "No CloudWatch log group configured for this service".to_string(),
+        ))
+    } else {
+        Err(AppError::TaskDefinition(format!(
+            "Unsupported log driver: {}",
+            unsupported_drivers.join(", ")
+        )))
+    }
+}
+
+// What the service runs with, for lining errors up against the limits (an
+// OOM kill near the memory limit, say). Container memory is in MiB and CPU in
+// CPU units; the task-level values are kept as the strings ECS returns.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ServiceInfo {
+    pub desired_count: i32,
+    pub running_count: i32,
+    pub task_cpu: Option<String>,
+    pub task_memory: Option<String>,
+    pub containers: Vec<ContainerInfo>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ContainerInfo {
+    pub name: String,
+    pub cpu: i32,
+    pub memory: Option<i32>,
+    pub memory_reservation: Option<i32>,
+}
+
+pub fn service_info(service: &Service, task_def: &TaskDefinition) -> ServiceInfo {
+    ServiceInfo {
+        desired_count: service.desired_count(),
+        running_count: service.running_count(),
+        task_cpu: task_def.cpu().map(String::from),
+        task_memory: task_def.memory().map(String::from),
+        containers: task_def
+            .container_definitions()
+            .iter()
+            .map(|c| ContainerInfo {
+                name: c.name().unwrap_or_default().to_string(),
+                cpu: c.cpu(),
+                memory: c.memory(),
+                memory_reservation: c.memory_reservation(),
+            })
+            .collect(),
+    }
+}
+
+const LOG_EVENT_LIMIT: i32 = 500;
+
+// Epoch-millis bounds passed to `filter_log_events`; unset ends are open.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TimeWindow {
+    pub start: Option<i64>,
+    pub end: Option<i64>,
 }
 
+impl TimeWindow {
+    pub fn is_set(&self) -> bool {
+        self.start.is_some() || self.end.is_some()
+    }
+
+    pub fn label(&self) -> String {
+        let format = |millis: Option<i64>, open: &str| {
+            millis
+                .and_then(|millis| Local.timestamp_millis_opt(millis).single())
+                .map_or(open.to_string(), |time| {
+                    time.format("%Y-%m-%d %H:%M:%S").to_string()
+                })
+        };
+        format!(
+            "{} → {}",
+            format(self.start, "start"),
+            format(self.end, "now")
+        )
+    }
+}
+
+// Accepts `<start>[..<end>]`, where each bound is either an age such as `15m`,
+// `2h` or `1d`, or a local `YYYY-MM-DD HH:MM[:SS]` time. An empty input clears
+// the window.
+pub fn parse_time_window(input: &str, now: DateTime<Local>) -> Result<TimeWindow, AppError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(TimeWindow::default());
+    }
+    let (start, end) = match input.split_once("..") {
+        Some((start, end)) => (start.trim(), end.trim()),
+        None => (input, ""),
+    };
+    let window = TimeWindow {
+        start: Some(parse_time_bound(start, now)?),
+        end: if end.is_empty() {
+            None
+        } else {
+            Some(parse_time_bound(end, now)?)
+        },
+    };
+    match window {
+        TimeWindow {
+            start: Some(start),
+            end: Some(end),
+        } if start >= end => Err(AppError::Parse(
+            "The start of the window must be before its end".to_string(),
+        )),
+        window => Ok(window),
+    }
+}
+
+fn parse_time_bound(bound: &str, now: DateTime<Local>) -> Result<i64, AppError> {
+    let invalid = || {
+        AppError::Parse(format!(
+            "Couldn't read \"{}\" as a time; use 15m, 2h, 1d or YYYY-MM-DD HH:MM",
+            bound
+        ))
+    };
+    let unit_seconds: Option<i64> = match bound.chars().last() {
+        Some('s') => Some(1),
+        Some('m') => Some(60),
+        Some('h') => Some(60 * 60),
+        Some('d') => Some(60 * 60 * 24),
+        _ => None,
+    };
+    if let Some(unit_seconds) = unit_seconds
+        && let Ok(amount) = bound[..bound.len() - 1].parse::<i64>()
+    {
+        // a negative age would put the bound in the future
+        return unit_seconds
+            .checked_mul(1000)
+            .and_then(|unit_millis| amount.checked_mul(unit_millis))
+            .filter(|_| amount > 0)
+            .and_then(|millis| now.timestamp_millis().checked_sub(millis))
+            .ok_or_else(invalid);
+    }
+    let naive = NaiveDateTime::parse_from_str(bound, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(bound, "%Y-%m-%d %H:%M"))
+        .map_err(|_| invalid())?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|time| time.timestamp_millis())
+        .ok_or_else(invalid)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogEvent {
+    pub timestamp: i64,
+    pub message: String,
+    pub stream: Option<String>,
+    pub log_group: String,
+}
+
+impl LogEvent {
+    pub fn is_stderr(&self) -> bool {
+        self.stream.as_deref().is_some_and(is_stderr_stream)
+    }
+}
+
+pub struct LogPage {
+    pub events: Vec<LogEvent>,
+    pub truncated: bool,
+    // one per log group read, in the order they were passed
+    pub next_tokens: Vec<Option<String>>,
+}
+
+// Reads a page from each log group, starting at its entry in `next_tokens`
+// (missing entries start from the top), and merges the events by timestamp.
 pub async fn get_logs(
     cw_client: &cloudwatch::Client,
-    log_group: &String,
-) -> Result<Vec<String>, cloudwatch::Error> {
+    log_groups: &[String],
+    log_stream_prefix: Option<&str>,
+    filter_pattern: Option<&str>,
+    window: TimeWindow,
+    next_tokens: &[Option<String>],
+) -> Result<LogPage, AppError> {
+    let pages = future::try_join_all(log_groups.iter().enumerate().map(|(i, log_group)| {
+        get_log_group_page(
+            cw_client,
+            log_group,
+            log_stream_prefix,
+            filter_pattern,
+            window,
+            next_tokens.get(i).cloned().flatten(),
+        )
+    }))
+    .await?;
+    let mut events = Vec::new();
+    let mut next_tokens = Vec::with_capacity(pages.len());
+    let mut truncated = false;
+    for (group_events, next_token) in pages {
+        truncated |= next_token.is_some() || group_events.len() >= LOG_EVENT_LIMIT as usize;
+        events.extend(group_events);
+        next_tokens.push(next_token);
+    }
+    events.sort_by_key(|event| event.timestamp);
+    Ok(LogPage {
+        events,
+        truncated,
+        next_tokens,
+    })
+}
+
+async fn get_log_group_page(
+    cw_client: &cloudwatch::Client,
+    log_group: &str,
+    log_stream_prefix: Option<&str>,
+    filter_pattern: Option<&str>,
+    window: TimeWindow,
+    next_token: Option<String>,
+) -> Result<(Vec<LogEvent>, Option<String>), AppError> {
     let log_events = cw_client
         .filter_log_events()
         .log_group_name(log_group)
-        .limit(500)
+        .set_log_stream_name_prefix(log_stream_prefix.map(str::to_string))
+        .set_filter_pattern(filter_pattern.map(str::to_string))
+        .set_start_time(window.start)
+        .set_end_time(window.end)
+        .set_next_token(next_token)
+        .limit(LOG_EVENT_LIMIT)
         .send()
-        .await;
-    let mut logs = Vec::new();
-    if let Some(events) = log_events.unwrap().events {
-        for event in events {
-            logs.push(format!(
-                "[{}] {}",
-                event.timestamp.unwrap(),
-                event.message.unwrap()
-            ));
+        .await
+        .map_err(cloudwatch::Error::from)?;
+    let next_token = log_events.next_token.clone();
+    // Events interleave across streams, so order them by timestamp.
+    let mut events: Vec<LogEvent> = log_events
+        .events
+        .unwrap_or_default()
+        .into_iter()
+        .map(|event| LogEvent {
+            timestamp: event.timestamp.unwrap_or_default(),
+            message: event.message.unwrap_or_default(),
+            stream: event.log_stream_name,
+            log_group: log_group.to_string(),
+        })
+        .collect();
+    events.sort_by_key(|event| event.timestamp);
+    Ok((events, next_token))
+}
+
+const LOG_STREAM_LIMIT: i32 = 50;
+
+pub struct LogStreamInfo {
+    pub name: String,
+    pub last_event: Option<i64>,
+}
+
+// The group's most recently written streams, newest first.
+pub async fn get_log_streams(
+    cw_client: &cloudwatch::Client,
+    log_group: &str,
+) -> Result<Vec<LogStreamInfo>, AppError> {
+    let output = cw_client
+        .describe_log_streams()
+        .log_group_name(log_group)
+        .order_by(cloudwatch::types::OrderBy::LastEventTime)
+        .descending(true)
+        .limit(LOG_STREAM_LIMIT)
+        .send()
+        .await
+        .map_err(cloudwatch::Error::from)?;
+    Ok(output
+        .log_streams()
+        .iter()
+        .map(|stream| LogStreamInfo {
+            name: stream.log_stream_name().unwrap_or_default().to_string(),
+            last_event: stream.last_event_timestamp(),
+        })
+        .collect())
+}
+
+// The latest events of a single stream, oldest first.
+pub async fn get_stream_events(
+    cw_client: &cloudwatch::Client,
+    log_group: &str,
+    log_stream: &str,
+) -> Result<Vec<LogEvent>, AppError> {
+    let output = cw_client
+        .get_log_events()
+        .log_group_name(log_group)
+        .log_stream_name(log_stream)
+        .start_from_head(false)
+        .limit(LOG_EVENT_LIMIT)
+        .send()
+        .await
+        .map_err(cloudwatch::Error::from)?;
+    Ok(output
+        .events()
+        .iter()
+        .map(|event| LogEvent {
+            timestamp: event.timestamp().unwrap_or_default(),
+            message: event.message().unwrap_or_default().to_string(),
+            stream: Some(log_stream.to_string()),
+            log_group: log_group.to_string(),
+        })
+        .collect())
+}
+
+// awslogs doesn't tag the stream an event came from, but setups that split the
+// output (FireLens, custom prefixes) name their stderr streams accordingly.
+pub fn is_stderr_stream(log_stream_name: &str) -> bool {
+    log_stream_name.to_lowercase().contains("stderr")
+}
+
+// The console expects the log group percent-encoded twice, with `%` written as `$25`.
+pub fn log_group_console_url(region: &str, log_group: &str) -> String {
+    let encoded: String = log_group
+        .bytes()
+        .map(|b| match b {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' | b'.' => (b as char).to_string(),
+            _ => format!("$25{:02X}", b),
+        })
+        .collect();
+    format!(
+        "https://{region}.console.aws.amazon.com/cloudwatch/home?region={region}#logsV2:log-groups/log-group/{encoded}"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_ecs::types::Failure;
+
+    #[test]
+    fn config_only_role_profiles_are_listed() {
+        let config = "[default]\nregion = us-east-1\n\n\
+            [profile cross-account]\n\
+            role_arn = arn:aws:iam::123456789012:role/ReadOnly\n\
+            source_profile = default\n\n\
+            [sso-session corp]\nsso_region = us-east-1\n";
+        assert_eq!(
+            parse_profiles(config, true),
+            vec![
+                ("default".to_string(), Some("us-east-1".to_string())),
+                ("cross-account".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_describe_services_output_yields_no_services() {
+        let output = DescribeServicesOutput::builder().build();
+        assert!(services_from_output(output).is_empty());
+    }
+
+    #[test]
+    fn describe_failures_are_reported_by_service_name() {
+        let output = DescribeServicesOutput::builder()
+            .failures(
+                Failure::builder()
+                    .arn("arn:aws:ecs:us-east-1:123456789012:service/prod/old-api")
+                    .reason("MISSING")
+                    .build(),
+            )
+            .build();
+        assert_eq!(
+            describe_failures_message(&output).as_deref(),
+            Some("1 service(s) couldn't be described: old-api (MISSING)")
+        );
+        assert_eq!(
+            describe_failures_message(&DescribeServicesOutput::builder().build()),
+            None
+        );
+    }
+
+    #[test]
+    fn denied_action_is_read_from_access_denied_errors() {
+        let message = "AccessDeniedException: User: arn:aws:sts::123456789012:assumed-role/ReadOnly/me is not authorized to perform: ecs:ListServices on resource: *";
+        assert!(is_access_denied(message));
+        assert_eq!(denied_action(message), Some("ecs:ListServices"));
+        assert_eq!(denied_action("AccessDeniedException: denied"), None);
+        assert!(!is_access_denied("dispatch failure"));
+    }
+
+    #[test]
+    fn stderr_streams_are_recognised() {
+        assert!(is_stderr_stream("firelens/app-STDERR/abc123"));
+        assert!(!is_stderr_stream("ecs/app/abc123"));
+    }
+
+    #[test]
+    fn time_window_accepts_ages_and_rejects_reversed_bounds() {
+        let now = Local::now();
+        let window = parse_time_window("2h..1h", now).unwrap();
+        assert_eq!(
+            window.start,
+            Some(now.timestamp_millis() - 2 * 60 * 60 * 1000)
+        );
+        assert_eq!(window.end, Some(now.timestamp_millis() - 60 * 60 * 1000));
+        assert_eq!(
+            parse_time_window(" ", now).ok(),
+            Some(TimeWindow::default())
+        );
+        assert!(parse_time_window("1h..2h", now).is_err());
+        assert!(parse_time_window("yesterday", now).is_err());
+    }
+
+    #[test]
+    fn time_window_rejects_negative_and_overflowing_ages() {
+        let now = Local::now();
+        assert!(parse_time_window("-5m", now).is_err());
+        assert!(parse_time_window("0h", now).is_err());
+        assert!(parse_time_window("99999999999999999d", now).is_err());
+        assert!(parse_time_window("2h..-1h", now).is_err());
+    }
+
+    #[test]
+    fn cluster_arns_are_described_in_chunks_of_100() {
+        let arns: Vec<String> = (0..150)
+            .map(|i| format!("arn:aws:ecs:us-east-1:123456789012:cluster/cluster-{}", i))
+            .collect();
+        let chunks = arn_chunks(&arns, DESCRIBE_CLUSTERS_LIMIT);
+        assert_eq!(
+            chunks.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![100, 50]
+        );
+        assert_eq!(chunks.concat(), arns);
+    }
+
+    // Hands out 25 ARNs, out of order, over three list_services pages and
+    // records the size of every describe_services call.
+    struct FakeServices {
+        described: std::sync::Mutex<Vec<usize>>,
+    }
+
+    impl ServiceApi for FakeServices {
+        async fn list_services_page(
+            &self,
+            _cluster_name: &str,
+            next_token: Option<String>,
+        ) -> Result<(Vec<String>, Option<String>), AppError> {
+            let page: usize = next_token.as_deref().map_or(0, |t| t.parse().unwrap());
+            let arns = (0..25)
+                .rev()
+                .skip(page * 10)
+                .take(10)
+                .map(|i| {
+                    format!(
+                        "arn:aws:ecs:us-east-1:123456789012:service/web/svc-{:02}",
+                        i
+                    )
+                })
+                .collect();
+            Ok((arns, (page < 2).then(|| (page + 1).to_string())))
+        }
+
+        async fn describe_services(
+            &self,
+            _cluster_name: &str,
+            arns: Vec<String>,
+        ) -> Result<DescribeServicesOutput, AppError> {
+            self.described.lock().unwrap().push(arns.len());
+            let services = arns
+                .iter()
+                .map(|arn| {
+                    Service::builder()
+                        .service_arn(arn)
+                        .service_name(arn.rsplit('/').next().unwrap())
+                        .build()
+                })
+                .collect();
+            Ok(DescribeServicesOutput::builder()
+                .set_services(Some(services))
+                .build())
         }
     }
 
-    Ok(logs)
+    #[tokio::test]
+    async fn services_are_listed_across_pages_and_described_in_chunks() {
+        let fake = FakeServices {
+            described: Default::default(),
+        };
+        let output = collect_services(&fake, "web", |_, _| {}).await.unwrap();
+        assert_eq!(*fake.described.lock().unwrap(), vec![10, 10, 5]);
+        let names: Vec<&str> = output
+            .services()
+            .iter()
+            .filter_map(|s| s.service_name())
+            .collect();
+        let expected: Vec<String> = (0..25).map(|i| format!("svc-{:02}", i)).collect();
+        assert_eq!(names, expected);
+    }
+
+    #[test]
+    fn region_is_read_from_arns() {
+        assert_eq!(
+            arn_region("arn:aws:ecs:eu-west-1:123456789012:service/web/api"),
+            Some("eu-west-1")
+        );
+        assert_eq!(arn_region("web"), None);
+    }
+
+    #[test]
+    fn console_url_encodes_log_group() {
+        assert_eq!(
+            log_group_console_url("eu-west-1", "/ecs/my-app"),
+            "https://eu-west-1.console.aws.amazon.com/cloudwatch/home?region=eu-west-1#logsV2:log-groups/log-group/$252Fecs$252Fmy-app"
+        );
+    }
 }
@@ -0,0 +1,41 @@
+use clap::Parser;
+
+#[derive(Debug, Parser)]
+#[command(version, about = "TUI for viewing logs of AWS ECS services")]
+pub struct Cli {
+    /// AWS profile to use
+    #[arg(long)]
+    pub profile: Option<String>,
+
+    /// ECS cluster to open (requires --profile)
+    #[arg(long, requires = "profile", conflicts_with = "log_group")]
+    pub cluster: Option<String>,
+
+    /// ECS service to tail; repeat to merge several services (requires --cluster)
+    #[arg(long, requires = "cluster")]
+    pub service: Vec<String>,
+
+    /// CloudWatch log group to tail directly, skipping ECS lookups (requires --profile)
+    #[arg(long, requires = "profile")]
+    pub log_group: Option<String>,
+
+    /// Colour scheme to use instead of the configured one: dracula, solarized-dark, gruvbox or light
+    #[arg(long)]
+    pub theme: Option<String>,
+
+    /// Only list profiles whose name contains this text [env: LAZYLOGGER_PROFILE_FILTER]
+    #[arg(long)]
+    pub profile_filter: Option<String>,
+
+    /// Leave the mouse to the terminal so its native text selection keeps working
+    #[arg(long)]
+    pub no_mouse: bool,
+
+    /// Draw in the main screen instead of the alternate one, leaving the last frame in scrollback on exit
+    #[arg(long)]
+    pub no_alt_screen: bool,
+
+    /// Show canned profiles, clusters, services and logs without calling AWS
+    #[arg(long, conflicts_with_all = ["profile", "log_group"])]
+    pub demo: bool,
+}
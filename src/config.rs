@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    error, fs,
+    path::PathBuf,
+};
+use toml_edit::{Decor, DocumentMut, Item, Table};
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub tick_rate_ms: u64,
+    pub theme: Option<String>,
+    pub timestamp_format: Option<String>,
+    // profile names or account IDs that get the production warning banner
+    pub prod_profiles: HashSet<String>,
+    // false leaves the mouse to the terminal for native text selection
+    pub mouse: bool,
+    pub keys: KeysConfig,
+    pub bookmarks: Vec<Bookmark>,
+    // service name -> container whose log group is tailed
+    pub container_overrides: HashMap<String, String>,
+    pub popup: PopupConfig,
+    // pages of logs loaded ahead when scrolling near the end; 0 turns it off
+    pub prefetch_pages: u32,
+    // regexes for the load balancer health checks that H hides
+    pub health_check_patterns: Vec<String>,
+    // JSON fields shown as columns for structured log lines, e.g. ["level", "msg"]
+    pub columns: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            tick_rate_ms: 250,
+            theme: None,
+            timestamp_format: None,
+            prod_profiles: HashSet::new(),
+            mouse: true,
+            keys: KeysConfig::default(),
+            bookmarks: Vec::new(),
+            container_overrides: HashMap::new(),
+            popup: PopupConfig::default(),
+            prefetch_pages: 1,
+            health_check_patterns: ["/health", "/ping", "ELB-HealthChecker"]
+                .map(String::from)
+                .to_vec(),
+            columns: Vec::new(),
+        }
+    }
+}
+
+// Size of the data source popup, as a percentage of the terminal, and the
+// width of its profile, cluster and service boxes as percentages of the popup.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default)]
+pub struct PopupConfig {
+    pub width: u16,
+    pub height: u16,
+    pub columns: [u16; 3],
+}
+
+impl Default for PopupConfig {
+    fn default() -> Self {
+        PopupConfig {
+            width: 60,
+            height: 25,
+            columns: [30, 30, 30],
+        }
+    }
+}
+
+// Each action takes a key name such as "q", "j", "down" or "enter".
+// `preset = "vim"` switches the defaults to vim-style navigation.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct KeysConfig {
+    pub preset: Option<String>,
+    pub quit: Option<String>,
+    pub config: Option<String>,
+    pub refresh: Option<String>,
+    pub up: Option<String>,
+    pub down: Option<String>,
+    pub focus: Option<String>,
+    pub enter: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Bookmark {
+    pub profile: String,
+    pub cluster: String,
+    pub service: String,
+}
+
+impl Bookmark {
+    pub fn label(&self) -> String {
+        format!("{} / {} / {}", self.profile, self.cluster, self.service)
+    }
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("lazylogger").join("config.toml"))
+}
+
+pub fn load_config() -> Result<Config, Box<dyn error::Error>> {
+    let Some(path) = config_path() else {
+        return Ok(Config::default());
+    };
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+    let contents = fs::read_to_string(&path)?;
+    let config = toml::from_str(&contents)
+        .map_err(|e| format!("invalid config file {}: {}", path.display(), e))?;
+    Ok(config)
+}
+
+pub fn save_bookmarks(bookmarks: &[Bookmark]) -> Result<(), Box<dyn error::Error>> {
+    save_setting("bookmarks", toml::Value::try_from(bookmarks)?)
+}
+
+pub fn save_container_overrides(
+    overrides: &HashMap<String, String>,
+) -> Result<(), Box<dyn error::Error>> {
+    save_setting("container_overrides", toml::Value::try_from(overrides)?)
+}
+
+pub fn save_theme(name: &str) -> Result<(), Box<dyn error::Error>> {
+    save_setting("theme", toml::Value::from(name))
+}
+
+// Only the given key is replaced, in place; the rest of the file is written
+// back as the user left it, comments and key order included.
+fn save_setting(key: &str, value: toml::Value) -> Result<(), Box<dyn error::Error>> {
+    let path = config_path().ok_or("could not determine config directory")?;
+    let mut document = if path.exists() {
+        fs::read_to_string(&path)?.parse::<DocumentMut>()?
+    } else {
+        DocumentMut::new()
+    };
+    // rendered the way toml writes it on its own ([table] for a map, [[table]]
+    // for a list of them) and then moved into the document
+    let mut rendered = toml::Table::new();
+    rendered.insert(key.to_string(), value);
+    let mut rendered = toml::to_string(&rendered)?.parse::<DocumentMut>()?;
+    let mut item = rendered.remove(key).unwrap_or_default();
+    match document.get_key_value(key) {
+        // a plain value keeps its key and the comments around it
+        Some((old_key, Item::Value(old))) if item.is_value() => {
+            let old_key = old_key.clone();
+            if let Item::Value(new) = &mut item {
+                *new.decor_mut() = old.decor().clone();
+            }
+            document.insert_formatted(&old_key, item);
+        }
+        old => {
+            // a table keeps its place and the comments above it; a new one goes
+            // after everything already in the file
+            let old_table = match old {
+                Some((_, Item::Table(table))) => Some(table),
+                Some((_, Item::ArrayOfTables(tables))) => tables.get(0),
+                _ => None,
+            };
+            let position = old_table
+                .and_then(|table| table.position())
+                .unwrap_or(isize::MAX);
+            let decor = match old_table {
+                Some(table) => table.decor().clone(),
+                None if document.is_empty() => Decor::default(),
+                None => Decor::new("\n", ""),
+            };
+            for (i, table) in tables_mut(&mut item).into_iter().enumerate() {
+                table.set_position(position);
+                if i == 0 {
+                    *table.decor_mut() = decor.clone();
+                }
+            }
+            document.insert(key, item);
+        }
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, document.to_string())?;
+    Ok(())
+}
+
+fn tables_mut(item: &mut Item) -> Vec<&mut Table> {
+    match item {
+        Item::Table(table) => vec![table],
+        Item::ArrayOfTables(tables) => tables.iter_mut().collect(),
+        _ => Vec::new(),
+    }
+}
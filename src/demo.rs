@@ -0,0 +1,77 @@
+use crate::aws_utils::LogEvent;
+use aws_sdk_ecs::types::{Cluster, LaunchType, Service};
+
+// Canned data for --demo, which stands in for every AWS call so the UI can be
+// shown (or worked on) without credentials.
+pub const ACCOUNT: &str = "123456789012";
+const REGION: &str = "us-east-1";
+
+pub fn profiles() -> Vec<(String, Option<String>)> {
+    ["dev", "staging", "prod"]
+        .into_iter()
+        .map(|name| (name.to_string(), Some(REGION.to_string())))
+        .collect()
+}
+
+pub fn clusters() -> Vec<Cluster> {
+    ["web", "workers"]
+        .into_iter()
+        .map(|name| {
+            Cluster::builder()
+                .cluster_name(name)
+                .cluster_arn(format!(
+                    "arn:aws:ecs:{}:{}:cluster/{}",
+                    REGION, ACCOUNT, name
+                ))
+                .build()
+        })
+        .collect()
+}
+
+pub fn services(cluster: &str) -> Vec<Service> {
+    // (name, desired, running); admin is one task short so it shows as degraded
+    let services: &[(&str, i32, i32)] = match cluster {
+        "web" => &[("admin", 1, 0), ("api", 3, 3), ("frontend", 2, 2)],
+        _ => &[("billing-worker", 2, 2), ("email-worker", 1, 1)],
+    };
+    services
+        .iter()
+        .map(|&(name, desired, running)| {
+            Service::builder()
+                .service_name(name)
+                .service_arn(format!(
+                    "arn:aws:ecs:{}:{}:service/{}/{}",
+                    REGION, ACCOUNT, cluster, name
+                ))
+                .desired_count(desired)
+                .running_count(running)
+                .launch_type(LaunchType::Fargate)
+                .build()
+        })
+        .collect()
+}
+
+// A couple of minutes of made-up request logs ending at `now` (epoch millis).
+pub fn log_events(service: &str, now: i64) -> Vec<LogEvent> {
+    let messages = [
+        "\x1b[32mINFO\x1b[0m server listening on 0.0.0.0:8080",
+        "\x1b[32mINFO\x1b[0m GET /health 200 2ms",
+        "\x1b[32mINFO\x1b[0m GET /api/orders?page=1 200 48ms",
+        r#"{"level":"info","msg":"cache warmed","entries":1204}"#,
+        "\x1b[33mWARN\x1b[0m slow query on orders (812ms)",
+        "\x1b[32mINFO\x1b[0m POST /api/orders 201 95ms",
+        "\x1b[31mERROR\x1b[0m payment provider timed out after 5000ms",
+        "\x1b[32mINFO\x1b[0m retrying payment (attempt 2)",
+        "\x1b[32mINFO\x1b[0m POST /api/payments 200 310ms",
+        "\x1b[32mINFO\x1b[0m GET /health 200 1ms",
+    ];
+    let count = messages.len() * 4;
+    (0..count)
+        .map(|i| LogEvent {
+            timestamp: now - (count - i) as i64 * 3_000,
+            message: messages[i % messages.len()].to_string(),
+            stream: Some(format!("ecs/{}/0f1e2d3c4b5a", service)),
+            log_group: format!("/ecs/{}", service),
+        })
+        .collect()
+}
@@ -0,0 +1,42 @@
+use aws_sdk_cloudwatchlogs as cloudwatch;
+use std::io;
+use thiserror::Error;
+
+// Everything the AWS helpers can fail with. The SDK variants are transparent so
+// `DisplayErrorContext` still walks their full cause chain for the error banner,
+// and boxed because the SDK errors are large enough to bloat every `Result`.
+#[derive(Debug, Error)]
+pub enum AppError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("{0}")]
+    Credentials(String),
+    #[error(transparent)]
+    Ecs(Box<aws_sdk_ecs::Error>),
+    #[error(transparent)]
+    CloudWatch(Box<cloudwatch::Error>),
+    #[error(transparent)]
+    Sts(Box<aws_sdk_sts::Error>),
+    #[error("{0}")]
+    TaskDefinition(String),
+    #[error("{0}")]
+    Parse(String),
+}
+
+impl From<aws_sdk_ecs::Error> for AppError {
+    fn from(e: aws_sdk_ecs::Error) -> Self {
+        AppError::Ecs(Box::new(e))
+    }
+}
+
+impl From<cloudwatch::Error> for AppError {
+    fn from(e: cloudwatch::Error) -> Self {
+        AppError::CloudWatch(Box::new(e))
+    }
+}
+
+impl From<aws_sdk_sts::Error> for AppError {
+    fn from(e: aws_sdk_sts::Error) -> Self {
+        AppError::Sts(Box::new(e))
+    }
+}
@@ -1,38 +1,468 @@
 use crate::app::{App, CurrentScreen, OptionList, SettingConfig};
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::aws_utils::parse_time_window;
+use crate::config::KeysConfig;
+use chrono::{Local, Utc};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+use std::{
+    error,
+    time::{Duration, Instant},
+};
 
-pub fn main_screen_keymaps(key: KeyEvent, app: &mut App) {
-    match key.code {
-        KeyCode::Char('c') => {
+const HORIZONTAL_SCROLL_STEP: usize = 8;
+const REFRESH_DEBOUNCE: Duration = Duration::from_secs(1);
+const DETAIL_PAGE: u16 = 10;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    Config,
+    Refresh,
+    Up,
+    Down,
+    Focus,
+    Enter,
+}
+
+#[derive(Clone, Copy)]
+pub struct KeyMap {
+    pub quit: KeyCode,
+    pub config: KeyCode,
+    pub refresh: KeyCode,
+    pub up: KeyCode,
+    pub down: KeyCode,
+    pub focus: KeyCode,
+    pub enter: KeyCode,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        KeyMap {
+            quit: KeyCode::Char('q'),
+            config: KeyCode::Char('c'),
+            refresh: KeyCode::Char('r'),
+            up: KeyCode::Up,
+            down: KeyCode::Down,
+            focus: KeyCode::Char('e'),
+            enter: KeyCode::Enter,
+        }
+    }
+}
+
+impl KeyMap {
+    pub fn vim() -> Self {
+        KeyMap {
+            up: KeyCode::Char('k'),
+            down: KeyCode::Char('j'),
+            ..KeyMap::default()
+        }
+    }
+
+    pub fn from_config(keys: &KeysConfig) -> Result<KeyMap, Box<dyn error::Error>> {
+        let mut keymap = match keys.preset.as_deref() {
+            None | Some("default") => KeyMap::default(),
+            Some("vim") => KeyMap::vim(),
+            Some(other) => return Err(format!("unknown key preset '{}'", other).into()),
+        };
+        for (action, name, slot) in [
+            ("quit", &keys.quit, &mut keymap.quit),
+            ("config", &keys.config, &mut keymap.config),
+            ("refresh", &keys.refresh, &mut keymap.refresh),
+            ("up", &keys.up, &mut keymap.up),
+            ("down", &keys.down, &mut keymap.down),
+            ("focus", &keys.focus, &mut keymap.focus),
+            ("enter", &keys.enter, &mut keymap.enter),
+        ] {
+            if let Some(name) = name {
+                *slot = parse_key_code(name)
+                    .ok_or_else(|| format!("unknown key '{}' for action '{}'", name, action))?;
+            }
+        }
+        Ok(keymap)
+    }
+
+    pub fn action(&self, code: KeyCode) -> Option<Action> {
+        [
+            (self.quit, Action::Quit),
+            (self.config, Action::Config),
+            (self.refresh, Action::Refresh),
+            (self.up, Action::Up),
+            (self.down, Action::Down),
+            (self.focus, Action::Focus),
+            (self.enter, Action::Enter),
+        ]
+        .into_iter()
+        .find(|(bound, _)| *bound == code)
+        .map(|(_, action)| action)
+        .or(match code {
+            // vim-style alternates that work alongside whatever up/down are bound to
+            KeyCode::Char('j') => Some(Action::Down),
+            KeyCode::Char('k') => Some(Action::Up),
+            _ => None,
+        })
+    }
+}
+
+pub fn parse_key_code(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+    match name.to_lowercase().as_str() {
+        "up" => Some(KeyCode::Up),
+        "down" => Some(KeyCode::Down),
+        "left" => Some(KeyCode::Left),
+        "right" => Some(KeyCode::Right),
+        "enter" => Some(KeyCode::Enter),
+        "esc" => Some(KeyCode::Esc),
+        "tab" => Some(KeyCode::Tab),
+        "backtab" => Some(KeyCode::BackTab),
+        "space" => Some(KeyCode::Char(' ')),
+        "backspace" => Some(KeyCode::Backspace),
+        "home" => Some(KeyCode::Home),
+        "end" => Some(KeyCode::End),
+        "pageup" => Some(KeyCode::PageUp),
+        "pagedown" => Some(KeyCode::PageDown),
+        other => other
+            .strip_prefix('f')
+            .and_then(|n| n.parse().ok())
+            .map(KeyCode::F),
+    }
+}
+
+pub fn key_name(code: KeyCode) -> String {
+    match code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        other => format!("{:?}", other),
+    }
+}
+
+// Ctrl-C quits from any screen, popups included.
+pub fn is_force_quit(key: &KeyEvent) -> bool {
+    key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
+pub fn main_screen_keymaps(key: KeyEvent, app: &mut App) -> std::io::Result<bool> {
+    // Shift-R already toggles regex search, so the full reload lives on Ctrl-R
+    if key.code == KeyCode::Char('r')
+        && key.modifiers.contains(KeyModifiers::CONTROL)
+        && !app.profile.is_empty()
+    {
+        app.refresh_all();
+        return Ok(false);
+    }
+    // While typing a search query every character belongs to the query,
+    // so this has to run before any of the (possibly remapped) actions.
+    app.status_message = None;
+    if app.prefix_mode {
+        prefix_keymaps(key, app);
+        return Ok(false);
+    }
+    if app.window_mode {
+        window_keymaps(key, app);
+        return Ok(false);
+    }
+    if app.pattern_mode {
+        pattern_keymaps(key, app);
+        return Ok(false);
+    }
+    if app.search_mode {
+        search_keymaps(key, app);
+        return Ok(false);
+    }
+    match app.keymap.action(key.code) {
+        Some(Action::Config) => {
+            app.marked_services.clear();
             app.current_screen = CurrentScreen::SettingConfig;
             app.setting_config = Some(SettingConfig::Profile);
         }
-        KeyCode::Char('q') => {
+        Some(Action::Quit) => {
             app.current_screen = CurrentScreen::Exiting;
         }
-        KeyCode::Char('e') => {
-            app.viewing_logs = !app.viewing_logs;
+        Some(Action::Focus) => {
+            app.toggle_focus();
         }
-        KeyCode::Char('r') => {
-            if app.viewing_logs {
-                app.service_events = OptionList::new();
+        Some(Action::Refresh) => {
+            // mashing or holding the key would refetch on every tick
+            let debounced = app
+                .last_refresh_request
+                .is_some_and(|last| last.elapsed() < REFRESH_DEBOUNCE);
+            if (app.viewing_logs || app.log_error.is_some()) && !debounced {
+                app.last_refresh_request = Some(Instant::now());
+                // a manual refresh doesn't wait out the offline backoff
+                app.retry_at = None;
+                app.reset_logs();
             }
         }
-        KeyCode::Down => {
-            if app.viewing_logs {
-                app.service_events.next();
+        Some(Action::Down) => {
+            if app.ecs_events_active() {
+                app.next_ecs_event();
+            } else if app.viewing_logs {
+                app.next_event();
+                app.prefetch_if_near_end();
             }
         }
-        KeyCode::Up => {
-            if app.viewing_logs {
-                app.service_events.previous();
+        Some(Action::Up) => {
+            if app.ecs_events_active() {
+                app.previous_ecs_event();
+            } else if app.viewing_logs {
+                app.previous_event();
             }
         }
-        KeyCode::Enter => {
+        Some(Action::Enter) => {
             if app.viewing_logs {
+                app.detail_scroll = 0;
                 app.current_screen = CurrentScreen::LogDetails;
             }
         }
+        None => match key.code {
+            KeyCode::Left if key.modifiers.contains(KeyModifiers::SHIFT) && app.viewing_logs => {
+                app.event_box.horizontal_scroll = app
+                    .event_box
+                    .horizontal_scroll
+                    .saturating_sub(HORIZONTAL_SCROLL_STEP);
+            }
+            KeyCode::Right if key.modifiers.contains(KeyModifiers::SHIFT) && app.viewing_logs => {
+                let longest_line = app
+                    .service_events
+                    .items
+                    .iter()
+                    .map(|event| event.text().chars().count())
+                    .max()
+                    .unwrap_or(0);
+                app.event_box.horizontal_scroll = app
+                    .event_box
+                    .horizontal_scroll
+                    .saturating_add(HORIZONTAL_SCROLL_STEP)
+                    .min(longest_line.saturating_sub(1));
+            }
+            KeyCode::Char('d') if !app.service.is_empty() => {
+                app.deployments.clear();
+                app.deployments_error = None;
+                app.deployments_refreshed = None;
+                app.current_screen = CurrentScreen::Deployments;
+            }
+            KeyCode::Char('L') if app.sso_login_needed => {
+                app.run_sso_login = true;
+            }
+            KeyCode::Char('S') => {
+                app.marked_services.clear();
+                app.current_screen = CurrentScreen::SettingConfig;
+                app.setting_config = Some(if !app.cluster.is_empty() {
+                    SettingConfig::Service
+                } else if !app.profile.is_empty() {
+                    SettingConfig::Cluster
+                } else {
+                    SettingConfig::Profile
+                });
+            }
+            KeyCode::Char('x') if !app.service.is_empty() => {
+                app.stopped_tasks.clear();
+                app.stopped_tasks_error = None;
+                app.stopped_tasks_refreshed = None;
+                app.current_screen = CurrentScreen::StoppedTasks;
+            }
+            KeyCode::Char('C') if !app.log_sources.is_empty() => {
+                app.cycle_container();
+            }
+            KeyCode::Char('o') if !app.log_sources.is_empty() => {
+                app.open_in_console();
+            }
+            KeyCode::Char('l') if !app.log_sources.is_empty() => {
+                app.open_log_streams();
+            }
+            KeyCode::Char('b') if !app.service.is_empty() => {
+                app.toggle_bookmark();
+            }
+            KeyCode::Char('i') if !app.log_sources.is_empty() => {
+                app.show_service_info = !app.show_service_info;
+            }
+            KeyCode::Char('M') if app.viewing_logs => {
+                let mark = Utc::now();
+                app.time_mark = Some(mark);
+                app.status_message = Some(format!(
+                    "Marked {}, older lines are dimmed",
+                    mark.with_timezone(&Local).format("%H:%M:%S")
+                ));
+            }
+            KeyCode::Char('t') if app.viewing_logs => {
+                app.timestamp_mode = app.timestamp_mode.next();
+            }
+            KeyCode::Char('f') if app.viewing_logs => {
+                app.display_format = app.display_format.next();
+            }
+            KeyCode::Char('Y') if app.viewing_logs => {
+                let text = app.visible_text();
+                if text.is_empty() {
+                    app.status_message = Some("No log lines to copy".to_string());
+                } else {
+                    app.copy_to_clipboard(text);
+                }
+            }
+            KeyCode::Char('J') if app.viewing_logs => {
+                if app.json_columns.is_empty() {
+                    app.status_message =
+                        Some("Set `columns` in the config file to show JSON fields".to_string());
+                } else {
+                    app.show_columns = !app.show_columns;
+                }
+            }
+            KeyCode::Char('H') if app.viewing_logs => {
+                app.toggle_health_checks();
+            }
+            KeyCode::Char('w') if app.viewing_logs => {
+                app.wrap_logs = !app.wrap_logs;
+            }
+            KeyCode::Char('m') if app.viewing_logs && app.logs_truncated => {
+                app.load_more_logs = true;
+            }
+            KeyCode::Char('s') if !app.service.is_empty() => {
+                app.toggle_split_view();
+            }
+            KeyCode::Char('v') if !app.service.is_empty() => {
+                app.toggle_event_source();
+            }
+            KeyCode::Tab if app.split_view => {
+                app.ecs_events_focused = !app.ecs_events_focused;
+            }
+            KeyCode::Char('T') => {
+                app.cycle_theme();
+            }
+            KeyCode::Char('F') if app.has_log_target() => {
+                app.pattern_input = app.filter_pattern.clone().unwrap_or_default();
+                app.pattern_mode = true;
+            }
+            KeyCode::Char('W') if app.has_log_target() => {
+                app.window_input.clear();
+                app.window_mode = true;
+            }
+            KeyCode::Char('p') if app.has_log_target() => {
+                app.prefix_input = app.stream_prefix.clone().unwrap_or_default();
+                app.prefix_mode = true;
+            }
+            KeyCode::Char('/') if app.viewing_logs => {
+                app.search_mode = true;
+            }
+            KeyCode::Char('R') if app.viewing_logs => {
+                app.use_regex = !app.use_regex;
+                app.update_search();
+            }
+            KeyCode::Char('n') if app.search_active() => {
+                app.next_event();
+            }
+            KeyCode::Char('N') if app.search_active() => {
+                app.previous_event();
+            }
+            KeyCode::Esc if app.search_active() => {
+                app.search_query.clear();
+                app.update_search();
+            }
+            _ => {}
+        },
+    }
+    Ok(false)
+}
+
+fn search_keymaps(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Esc => {
+            app.search_mode = false;
+            app.search_query.clear();
+            app.update_search();
+        }
+        KeyCode::Enter => {
+            app.search_mode = false;
+        }
+        KeyCode::Backspace => {
+            app.search_query.pop();
+            app.update_search();
+        }
+        KeyCode::Char(c) => {
+            app.search_query.push(c);
+            app.update_search();
+        }
+        KeyCode::Down => {
+            app.next_event();
+        }
+        KeyCode::Up => {
+            app.previous_event();
+        }
+        _ => {}
+    }
+}
+
+fn prefix_keymaps(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Esc => {
+            app.prefix_mode = false;
+        }
+        KeyCode::Enter => {
+            app.prefix_mode = false;
+            let prefix = app.prefix_input.trim();
+            app.stream_prefix = (!prefix.is_empty()).then(|| prefix.to_string());
+            app.reset_logs();
+        }
+        KeyCode::Backspace => {
+            app.prefix_input.pop();
+        }
+        KeyCode::Char(c) => {
+            app.prefix_input.push(c);
+        }
+        _ => {}
+    }
+}
+
+fn pattern_keymaps(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Esc => {
+            app.pattern_mode = false;
+        }
+        KeyCode::Enter => {
+            app.pattern_mode = false;
+            let pattern = app.pattern_input.trim();
+            app.filter_pattern = (!pattern.is_empty()).then(|| pattern.to_string());
+            app.reset_logs();
+        }
+        KeyCode::Backspace => {
+            app.pattern_input.pop();
+        }
+        KeyCode::Char(c) => {
+            app.pattern_input.push(c);
+        }
+        _ => {}
+    }
+}
+
+const WINDOW_PRESETS: [&str; 4] = ["15m", "1h", "6h", "24h"];
+
+fn window_keymaps(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Esc => {
+            app.window_mode = false;
+        }
+        KeyCode::Enter => match parse_time_window(&app.window_input, Local::now()) {
+            Ok(window) => {
+                app.window_mode = false;
+                app.time_window = window;
+                app.reset_logs();
+            }
+            Err(e) => app.status_message = Some(e.to_string()),
+        },
+        KeyCode::Tab => {
+            let next = WINDOW_PRESETS
+                .iter()
+                .position(|preset| *preset == app.window_input)
+                .map_or(0, |i| (i + 1) % WINDOW_PRESETS.len());
+            app.window_input = WINDOW_PRESETS[next].to_string();
+        }
+        KeyCode::Backspace => {
+            app.window_input.pop();
+        }
+        KeyCode::Char(c) => {
+            app.window_input.push(c);
+        }
         _ => {}
     }
 }
@@ -40,7 +470,7 @@ pub fn main_screen_keymaps(key: KeyEvent, app: &mut App) {
 pub fn exit_screen_keymaps(key: KeyEvent, app: &mut App) -> std::io::Result<bool> {
     match key.code {
         KeyCode::Char('y') => Ok(true),
-        KeyCode::Char('n') | KeyCode::Char('q') => {
+        code if code == KeyCode::Char('n') || code == app.keymap.quit => {
             app.current_screen = CurrentScreen::Main;
             Ok(false)
         }
@@ -50,51 +480,137 @@ pub fn exit_screen_keymaps(key: KeyEvent, app: &mut App) -> std::io::Result<bool
 
 pub fn log_details_keymaps(key: KeyEvent, app: &mut App) {
     match key.code {
-        KeyCode::Char('q') | KeyCode::Esc => {
+        code if code == app.keymap.quit || code == KeyCode::Esc => {
+            app.status_message = None;
             app.current_screen = CurrentScreen::Main;
         }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.detail_scroll = app.detail_scroll.saturating_add(1);
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.detail_scroll = app.detail_scroll.saturating_sub(1);
+        }
+        KeyCode::PageDown => {
+            app.detail_scroll = app.detail_scroll.saturating_add(DETAIL_PAGE);
+        }
+        KeyCode::PageUp => {
+            app.detail_scroll = app.detail_scroll.saturating_sub(DETAIL_PAGE);
+        }
+        KeyCode::Char('g') => {
+            app.detail_scroll = 0;
+        }
+        KeyCode::Char('y') => {
+            if let Some((_, _, message)) = app.selected_event() {
+                let message = message.to_string();
+                app.copy_to_clipboard(message);
+            }
+        }
         _ => {}
     }
 }
 
-pub fn setting_config_keymaps(key: KeyEvent, app: &mut App) {
+pub fn deployments_keymaps(key: KeyEvent, app: &mut App) {
     match key.code {
-        KeyCode::Esc => {
+        code if code == app.keymap.quit || code == KeyCode::Esc || code == KeyCode::Char('d') => {
             app.current_screen = CurrentScreen::Main;
-            app.setting_config = None;
         }
-        KeyCode::Tab => {
-            app.toggle_setting();
+        _ => {}
+    }
+}
+
+pub fn stopped_tasks_keymaps(key: KeyEvent, app: &mut App) {
+    match key.code {
+        code if code == app.keymap.quit || code == KeyCode::Esc || code == KeyCode::Char('x') => {
+            app.current_screen = CurrentScreen::Main;
+        }
+        KeyCode::Char('f') => {
+            app.crashed_only = !app.crashed_only;
         }
-        KeyCode::Char('q') => {
+        _ => {}
+    }
+}
+
+pub fn log_streams_keymaps(key: KeyEvent, app: &mut App) {
+    match key.code {
+        code if code == app.keymap.quit || code == KeyCode::Esc || code == KeyCode::Char('l') => {
             app.current_screen = CurrentScreen::Main;
-            app.setting_config = None;
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.log_streams.next();
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.log_streams.previous();
         }
         KeyCode::Enter => {
+            let stream = app.log_streams.selected().map(|stream| stream.name.clone());
+            if stream.is_some() {
+                app.view_log_stream(stream);
+            }
+        }
+        KeyCode::Backspace => {
+            app.view_log_stream(None);
+        }
+        _ => {}
+    }
+}
+
+pub fn setting_config_keymaps(key: KeyEvent, app: &mut App) {
+    match app.keymap.action(key.code) {
+        Some(Action::Quit) => {
+            app.current_screen = CurrentScreen::Main;
+            app.setting_config = None;
+        }
+        Some(Action::Refresh) => {
+            app.bust_cache();
+        }
+        Some(Action::Enter) => {
             if let Some(setting_config) = &app.setting_config {
                 match setting_config {
                     SettingConfig::Profile => {
                         if app.profiles.selected().is_some() {
                             app.profile = app.profiles.selected().unwrap().to_string();
+                            app.picker_error = None;
+                            app.status_message = None;
+                            app.cancel_loads();
                             app.setting_config = Some(SettingConfig::Cluster);
                             app.clusters = OptionList::new();
                             app.services = OptionList::new();
                             app.cluster.clear();
                             app.service.clear();
-                            app.service_events = OptionList::new();
+                            app.marked_services.clear();
+                            app.reset_logs();
                         }
                     }
                     SettingConfig::Cluster => {
                         if app.clusters.selected().is_some() {
                             app.cluster = app.clusters.selected().unwrap().to_string();
+                            app.picker_error = None;
+                            app.cancel_loads();
                             app.setting_config = Some(SettingConfig::Service);
                             app.services = OptionList::new();
                             app.service.clear();
+                            app.marked_services.clear();
+                            app.reset_logs();
                         }
                     }
                     SettingConfig::Service => {
                         if app.services.selected().is_some() {
-                            app.service = app.services.selected().unwrap().to_string();
+                            // Space-marked services are merged into one stream; otherwise
+                            // just the highlighted one is opened.
+                            app.service = if app.marked_services.is_empty() {
+                                vec![app.services.selected().unwrap().to_string()]
+                            } else {
+                                std::mem::take(&mut app.marked_services)
+                            };
+                            app.direct_log_group = None;
+                            app.reset_logs();
+                            app.current_screen = CurrentScreen::Main;
+                            app.setting_config = None;
+                        }
+                    }
+                    SettingConfig::Favorites => {
+                        if let Some(i) = app.favorites.state.selected() {
+                            app.open_bookmark(app.bookmarks[i].clone());
                             app.current_screen = CurrentScreen::Main;
                             app.setting_config = None;
                         }
@@ -102,91 +618,400 @@ pub fn setting_config_keymaps(key: KeyEvent, app: &mut App) {
                 }
             }
         }
-        KeyCode::Down => {
-            if let Some(setting_config) = &app.setting_config {
-                match setting_config {
-                    SettingConfig::Profile => {
-                        let previously_selected = app.profiles.state.selected();
-                        app.profiles.next();
-
-                        if app.profiles.state.selected() != previously_selected {
-                            app.profile_box.vertical_scroll =
-                                app.profile_box.vertical_scroll.saturating_add(1);
-                            app.profile_box.vertical_scroll_state = app
-                                .profile_box
-                                .vertical_scroll_state
-                                .position(app.profile_box.vertical_scroll);
-                        }
-                    }
-                    SettingConfig::Cluster => {
-                        let previously_selected = app.clusters.state.selected();
-                        app.clusters.next();
-                        if app.clusters.state.selected() != previously_selected {
-                            app.cluster_box.vertical_scroll =
-                                app.cluster_box.vertical_scroll.saturating_add(1);
-                            app.cluster_box.vertical_scroll_state = app
-                                .cluster_box
-                                .vertical_scroll_state
-                                .position(app.cluster_box.vertical_scroll);
-                        }
-                    }
-                    SettingConfig::Service => {
-                        let previously_selected = app.services.state.selected();
-                        app.services.next();
-                        if app.services.state.selected() != previously_selected {
-                            app.service_box.vertical_scroll =
-                                app.service_box.vertical_scroll.saturating_add(1);
-                            app.service_box.vertical_scroll_state = app
-                                .service_box
-                                .vertical_scroll_state
-                                .position(app.service_box.vertical_scroll);
+        Some(Action::Down) => {
+            next_setting_item(app);
+        }
+        Some(Action::Up) => {
+            previous_setting_item(app);
+        }
+        _ => match key.code {
+            KeyCode::Esc => {
+                app.current_screen = CurrentScreen::Main;
+                app.setting_config = None;
+            }
+            KeyCode::Tab | KeyCode::Char('l') => {
+                app.toggle_setting();
+            }
+            KeyCode::BackTab | KeyCode::Char('h') => {
+                app.toggle_setting_back();
+            }
+            KeyCode::Backspace => {
+                app.step_back_setting();
+            }
+            KeyCode::F(5) => {
+                app.bust_cache();
+            }
+            KeyCode::PageDown => {
+                page_setting_items(app, true);
+            }
+            KeyCode::PageUp => {
+                page_setting_items(app, false);
+            }
+            KeyCode::Char('L') if app.sso_login_needed => {
+                app.run_sso_login = true;
+            }
+            KeyCode::Char(' ') if matches!(app.setting_config, Some(SettingConfig::Service)) => {
+                if let Some(selected) = app.services.selected().cloned() {
+                    match app.marked_services.iter().position(|s| *s == selected) {
+                        Some(i) => {
+                            app.marked_services.remove(i);
                         }
+                        None => app.marked_services.push(selected),
                     }
                 }
             }
+            KeyCode::Char('s') if matches!(app.setting_config, Some(SettingConfig::Service)) => {
+                app.sort_services_by_health = !app.sort_services_by_health;
+                app.rebuild_services();
+            }
+            KeyCode::Char('f') if matches!(app.setting_config, Some(SettingConfig::Service)) => {
+                app.launch_type_filter = app.launch_type_filter.next();
+                app.rebuild_services();
+            }
+            _ => {}
+        },
+    }
+}
+
+fn next_setting_item(app: &mut App) {
+    if let Some(setting_config) = &app.setting_config {
+        match setting_config {
+            SettingConfig::Profile => {
+                app.profiles.next();
+            }
+            SettingConfig::Cluster => {
+                app.clusters.next();
+            }
+            SettingConfig::Service => {
+                app.services.next();
+            }
+            SettingConfig::Favorites => {
+                app.favorites.next();
+            }
         }
-        KeyCode::Up => {
-            if let Some(setting_config) = &app.setting_config {
-                match setting_config {
-                    SettingConfig::Profile => {
-                        let previously_selected = app.profiles.state.selected();
-                        app.profiles.previous();
-                        if app.profiles.state.selected() != previously_selected {
-                            app.profile_box.vertical_scroll =
-                                app.profile_box.vertical_scroll.saturating_sub(1);
-                            app.profile_box.vertical_scroll_state = app
-                                .profile_box
-                                .vertical_scroll_state
-                                .position(app.profile_box.vertical_scroll);
-                        }
-                    }
-                    SettingConfig::Cluster => {
-                        let previously_selected = app.clusters.state.selected();
-                        app.clusters.previous();
-                        if app.clusters.state.selected() != previously_selected {
-                            app.cluster_box.vertical_scroll =
-                                app.cluster_box.vertical_scroll.saturating_sub(1);
-                            app.cluster_box.vertical_scroll_state = app
-                                .cluster_box
-                                .vertical_scroll_state
-                                .position(app.cluster_box.vertical_scroll);
-                        }
+    }
+}
+
+fn previous_setting_item(app: &mut App) {
+    if let Some(setting_config) = &app.setting_config {
+        match setting_config {
+            SettingConfig::Profile => {
+                app.profiles.previous();
+            }
+            SettingConfig::Cluster => {
+                app.clusters.previous();
+            }
+            SettingConfig::Service => {
+                app.services.previous();
+            }
+            SettingConfig::Favorites => {
+                app.favorites.previous();
+            }
+        }
+    }
+}
+
+// A page is however many rows the box showed on the last draw, less its borders.
+fn page_setting_items(app: &mut App, down: bool) {
+    let Some(setting_config) = &app.setting_config else {
+        return;
+    };
+    let (list, area) = match setting_config {
+        SettingConfig::Profile => (&mut app.profiles, app.profile_box.area),
+        SettingConfig::Cluster => (&mut app.clusters, app.cluster_box.area),
+        SettingConfig::Service => (&mut app.services, app.service_box.area),
+        SettingConfig::Favorites => (&mut app.favorites, app.favorite_box.area),
+    };
+    let page = area.height.saturating_sub(2).max(1) as usize;
+    if down {
+        list.next_by(page);
+    } else {
+        list.previous_by(page);
+    }
+}
+
+pub fn mouse_events(mouse: MouseEvent, app: &mut App) {
+    let position = Position::new(mouse.column, mouse.row);
+    match app.current_screen {
+        CurrentScreen::Main => match mouse.kind {
+            MouseEventKind::ScrollDown
+                if app.ecs_events_shown() && app.ecs_event_box.area.contains(position) =>
+            {
+                app.next_ecs_event()
+            }
+            MouseEventKind::ScrollUp
+                if app.ecs_events_shown() && app.ecs_event_box.area.contains(position) =>
+            {
+                app.previous_ecs_event()
+            }
+            MouseEventKind::ScrollDown => {
+                app.next_event();
+                app.prefetch_if_near_end();
+            }
+            MouseEventKind::ScrollUp => app.previous_event(),
+            MouseEventKind::Down(MouseButton::Left) if app.event_box.area.contains(position) => {
+                if app.wrap_logs {
+                    if let Some(row) =
+                        clicked_row(app.event_box.area, mouse.row, app.wrap_state.offset())
+                    {
+                        app.viewing_logs = true;
+                        app.select_wrapped_row(row);
                     }
-                    SettingConfig::Service => {
-                        let previously_selected = app.services.state.selected();
-                        app.services.previous();
-                        if app.services.state.selected() != previously_selected {
-                            app.service_box.vertical_scroll =
-                                app.service_box.vertical_scroll.saturating_sub(1);
-                            app.service_box.vertical_scroll_state = app
-                                .service_box
-                                .vertical_scroll_state
-                                .position(app.service_box.vertical_scroll);
-                        }
+                    return;
+                }
+                let offset = if app.filter_active() {
+                    app.filtered_state.offset()
+                } else {
+                    app.service_events.state.offset()
+                };
+                if let Some(row) = clicked_row(app.event_box.area, mouse.row, offset) {
+                    if let Some(&i) = app.visible_event_indices().get(row) {
+                        app.viewing_logs = true;
+                        app.service_events.state.select(Some(i));
                     }
                 }
             }
-        }
+            _ => {}
+        },
+        CurrentScreen::SettingConfig => match mouse.kind {
+            MouseEventKind::ScrollDown => next_setting_item(app),
+            MouseEventKind::ScrollUp => previous_setting_item(app),
+            MouseEventKind::Down(MouseButton::Left) => {
+                if app.profile_box.area.contains(position) {
+                    app.setting_config = Some(SettingConfig::Profile);
+                    select_clicked_row(&mut app.profiles, app.profile_box.area, mouse.row);
+                } else if app.cluster_box.area.contains(position) {
+                    app.setting_config = Some(SettingConfig::Cluster);
+                    select_clicked_row(&mut app.clusters, app.cluster_box.area, mouse.row);
+                } else if app.service_box.area.contains(position) {
+                    app.setting_config = Some(SettingConfig::Service);
+                    select_clicked_row(&mut app.services, app.service_box.area, mouse.row);
+                } else if app.favorite_box.area.contains(position) {
+                    app.setting_config = Some(SettingConfig::Favorites);
+                    select_clicked_row(&mut app.favorites, app.favorite_box.area, mouse.row);
+                }
+            }
+            _ => {}
+        },
         _ => {}
     }
 }
+
+// Maps a terminal row to a list index, accounting for the block border and
+// how far the list has been scrolled.
+fn clicked_row(area: Rect, row: u16, offset: usize) -> Option<usize> {
+    let row = row.checked_sub(area.y + 1)?;
+    if row >= area.height.saturating_sub(2) {
+        return None;
+    }
+    Some(usize::from(row) + offset)
+}
+
+fn select_clicked_row<T>(list: &mut OptionList<T>, area: Rect, row: u16) {
+    if let Some(i) = clicked_row(area, row, list.state.offset()).filter(|&i| i < list.items.len()) {
+        list.state.select(Some(i));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::DisplayEvent;
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn options(items: &[&str]) -> OptionList<String> {
+        let mut list = OptionList::new();
+        list.items = items.iter().map(|item| item.to_string()).collect();
+        list.state.select(Some(0));
+        list
+    }
+
+    fn events(lines: &[(i64, &str)]) -> OptionList<DisplayEvent> {
+        let mut list = OptionList::new();
+        list.items = lines
+            .iter()
+            .map(|&(timestamp, message)| DisplayEvent {
+                timestamp,
+                message: message.to_string(),
+                ..DisplayEvent::default()
+            })
+            .collect();
+        list.state.select(Some(0));
+        list
+    }
+
+    #[test]
+    fn config_key_opens_the_profile_picker() {
+        let mut app = App::new();
+        assert!(!main_screen_keymaps(press(KeyCode::Char('c')), &mut app).unwrap());
+        assert!(matches!(app.current_screen, CurrentScreen::SettingConfig));
+        assert!(matches!(app.setting_config, Some(SettingConfig::Profile)));
+    }
+
+    #[test]
+    fn shift_s_jumps_back_to_the_service_picker() {
+        let mut app = App::new();
+        app.profile = "dev".to_string();
+        app.cluster = "main".to_string();
+        main_screen_keymaps(press(KeyCode::Char('S')), &mut app).unwrap();
+        assert!(matches!(app.current_screen, CurrentScreen::SettingConfig));
+        assert!(matches!(app.setting_config, Some(SettingConfig::Service)));
+    }
+
+    #[test]
+    fn repeated_refreshes_within_a_second_are_ignored() {
+        let mut app = App::new();
+        app.viewing_logs = true;
+        app.logs_loaded = true;
+        main_screen_keymaps(press(KeyCode::Char('r')), &mut app).unwrap();
+        assert!(!app.logs_loaded);
+
+        app.logs_loaded = true;
+        main_screen_keymaps(press(KeyCode::Char('r')), &mut app).unwrap();
+        assert!(app.logs_loaded);
+    }
+
+    #[test]
+    fn scrolling_back_through_ecs_events_pauses_their_refresh() {
+        let mut app = App::new();
+        app.split_view = true;
+        app.ecs_events_focused = true;
+        app.ecs_events = events(&[(1, "deployed"), (2, "steady"), (3, "draining")]);
+        app.ecs_events.state.select(Some(2));
+
+        main_screen_keymaps(press(KeyCode::Up), &mut app).unwrap();
+        assert!(app.ecs_refresh_paused());
+        main_screen_keymaps(press(KeyCode::Down), &mut app).unwrap();
+        assert!(!app.ecs_refresh_paused());
+    }
+
+    #[test]
+    fn refresh_remembers_a_line_picked_above_the_bottom() {
+        let mut app = App::new();
+        app.viewing_logs = true;
+        app.logs_loaded = true;
+        app.service_events = events(&[(1, "start"), (2, "request"), (3, "done")]);
+        app.service_events.state.select(Some(1));
+        main_screen_keymaps(press(KeyCode::Char('r')), &mut app).unwrap();
+        assert_eq!(
+            app.reselect_line.as_ref().map(|line| line.message.as_str()),
+            Some("request")
+        );
+    }
+
+    #[test]
+    fn ctrl_c_quits_from_the_main_screen() {
+        let key = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(is_force_quit(&key));
+        assert!(!is_force_quit(&press(KeyCode::Char('c'))));
+    }
+
+    #[test]
+    fn page_keys_move_the_picker_by_the_visible_rows() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::SettingConfig;
+        app.setting_config = Some(SettingConfig::Profile);
+        let names: Vec<String> = (0..25).map(|i| format!("profile-{}", i)).collect();
+        app.profiles = names.into_iter().collect();
+        // 10 rows inside the borders
+        app.profile_box.area = ratatui::layout::Rect::new(0, 0, 30, 12);
+
+        setting_config_keymaps(press(KeyCode::PageDown), &mut app);
+        assert_eq!(app.profiles.state.selected(), Some(10));
+        setting_config_keymaps(press(KeyCode::PageDown), &mut app);
+        setting_config_keymaps(press(KeyCode::PageDown), &mut app);
+        assert_eq!(app.profiles.state.selected(), Some(24));
+        setting_config_keymaps(press(KeyCode::PageUp), &mut app);
+        assert_eq!(app.profiles.state.selected(), Some(14));
+    }
+
+    #[test]
+    fn enter_walks_from_profile_to_cluster_to_service() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::SettingConfig;
+        app.setting_config = Some(SettingConfig::Profile);
+        app.profiles = options(&["dev", "prod"]);
+        app.profiles.next();
+
+        setting_config_keymaps(press(KeyCode::Enter), &mut app);
+        assert_eq!(app.profile, "prod");
+        assert!(matches!(app.setting_config, Some(SettingConfig::Cluster)));
+
+        app.clusters = options(&["main"]);
+        setting_config_keymaps(press(KeyCode::Enter), &mut app);
+        assert_eq!(app.cluster, "main");
+        assert!(matches!(app.setting_config, Some(SettingConfig::Service)));
+
+        app.services = options(&["api", "worker"]);
+        app.services.next();
+        setting_config_keymaps(press(KeyCode::Enter), &mut app);
+        assert_eq!(app.service, vec!["worker".to_string()]);
+        assert!(matches!(app.current_screen, CurrentScreen::Main));
+        assert!(app.setting_config.is_none());
+    }
+
+    #[test]
+    fn picking_a_new_profile_clears_the_cluster_and_service() {
+        let mut app = App::new();
+        app.setting_config = Some(SettingConfig::Profile);
+        app.cluster = "main".to_string();
+        app.service = vec!["api".to_string()];
+        app.profiles = options(&["dev"]);
+
+        setting_config_keymaps(press(KeyCode::Enter), &mut app);
+        assert!(app.cluster.is_empty());
+        assert!(app.service.is_empty());
+    }
+
+    #[test]
+    fn enter_without_a_selection_stays_put() {
+        let mut app = App::new();
+        app.setting_config = Some(SettingConfig::Cluster);
+        setting_config_keymaps(press(KeyCode::Enter), &mut app);
+        assert!(matches!(app.setting_config, Some(SettingConfig::Cluster)));
+    }
+
+    #[test]
+    fn backspace_goes_back_and_clears_later_picks() {
+        let mut app = App::new();
+        app.setting_config = Some(SettingConfig::Service);
+        app.profile = "dev".to_string();
+        app.cluster = "main".to_string();
+        app.service = vec!["api".to_string()];
+        app.services = options(&["api"]);
+
+        setting_config_keymaps(press(KeyCode::Backspace), &mut app);
+        assert!(matches!(app.setting_config, Some(SettingConfig::Cluster)));
+        assert_eq!(app.profile, "dev");
+        assert!(app.cluster.is_empty());
+        assert!(app.service.is_empty());
+        assert!(app.services.items.is_empty());
+
+        setting_config_keymaps(press(KeyCode::Backspace), &mut app);
+        assert!(matches!(app.setting_config, Some(SettingConfig::Profile)));
+        assert!(app.profile.is_empty());
+    }
+
+    #[test]
+    fn tab_and_shift_tab_cycle_the_boxes_and_esc_cancels() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::SettingConfig;
+        app.setting_config = Some(SettingConfig::Profile);
+
+        setting_config_keymaps(press(KeyCode::Tab), &mut app);
+        assert!(matches!(app.setting_config, Some(SettingConfig::Cluster)));
+        setting_config_keymaps(press(KeyCode::Tab), &mut app);
+        assert!(matches!(app.setting_config, Some(SettingConfig::Service)));
+        setting_config_keymaps(press(KeyCode::Tab), &mut app);
+        assert!(matches!(app.setting_config, Some(SettingConfig::Profile)));
+        setting_config_keymaps(press(KeyCode::BackTab), &mut app);
+        assert!(matches!(app.setting_config, Some(SettingConfig::Service)));
+
+        setting_config_keymaps(press(KeyCode::Esc), &mut app);
+        assert!(matches!(app.current_screen, CurrentScreen::Main));
+        assert!(app.setting_config.is_none());
+    }
+}
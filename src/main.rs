@@ -1,3 +1,4 @@
+use clap::Parser;
 use color_eyre::Result;
 use ratatui::{
     Terminal,
@@ -8,30 +9,53 @@ use ratatui::{
     },
     prelude::CrosstermBackend,
 };
-use std::{error, io};
+use std::{error::Error, io};
 mod app;
 use app::App;
+mod ansi;
 mod aws_utils;
+mod cli;
+mod config;
+mod demo;
+mod error;
 mod keymaps;
 mod ui;
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn error::Error>> {
+async fn main() -> Result<(), Box<dyn Error>> {
+    let cli = cli::Cli::parse();
+    let mut config = config::load_config()?;
+    if cli.theme.is_some() {
+        config.theme = cli.theme.clone();
+    }
+    if cli.no_mouse {
+        config.mouse = false;
+    }
+    let alt_screen = !cli.no_alt_screen;
+    let mut app = App::with_config(&config)?;
+    app.alt_screen = alt_screen;
+    app.start_with(cli).await;
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    if alt_screen {
+        execute!(stdout, EnterAlternateScreen)?;
+    }
+    if config.mouse {
+        execute!(stdout, EnableMouseCapture)?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App::new();
     app.run_app(&mut terminal).await?;
 
     disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
+    if alt_screen {
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    }
+    if config.mouse {
+        execute!(terminal.backend_mut(), DisableMouseCapture)?;
+    }
     terminal.show_cursor()?;
 
     Ok(())
@@ -1,12 +1,28 @@
-use crate::app::{App, CurrentScreen, SettingConfig, Theme};
+use crate::ansi::AnsiRanges;
+use crate::app::{
+    App, CurrentScreen, EventSource, LogFetchState, SettingConfig, Theme, event_rate,
+};
+use crate::aws_utils::{arn_region, service_is_degraded, task_crashed};
+use crate::keymaps::key_name;
+use aws_sdk_ecs::types::DeploymentRolloutState;
+use chrono::{Local, TimeZone, Utc};
 use ratatui::{
     Frame,
-    layout::{Constraint, Direction, Layout, Rect},
-    style::Style,
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, Clear, List, ListItem, Padding, Paragraph, Scrollbar, Wrap},
+    widgets::{
+        Block, Borders, Clear, List, ListItem, ListState, Padding, Paragraph, Scrollbar,
+        ScrollbarState, Sparkline, Wrap,
+    },
 };
 
+// Below this width the three picker columns are too cramped to read.
+const NARROW_WIDTH: u16 = 90;
+// below this the layout's percentages leave boxes with no room at all
+const MIN_WIDTH: u16 = 40;
+const MIN_HEIGHT: u16 = 10;
+
 pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -27,67 +43,647 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
-pub fn ui(frame: &mut Frame, app: &mut App) {
-    let background = Block::default().style(Style::default().bg(Theme::default().background));
-    frame.render_widget(background, frame.area());
+// Splits `text` into spans around the byte ranges in `matches` and the ANSI
+// `colors` (drawn over `style`), then drops the first `skip` characters for
+// horizontal scrolling.
+fn highlighted_line(
+    text: &str,
+    matches: &[(usize, usize)],
+    colors: &[(usize, usize, Style)],
+    skip: usize,
+    style: Style,
+    match_style: Style,
+) -> Line<'static> {
+    let mut bounds = vec![0, text.len()];
+    bounds.extend(matches.iter().flat_map(|&(start, end)| [start, end]));
+    bounds.extend(colors.iter().flat_map(|&(start, end, _)| [start, end]));
+    bounds.sort_unstable();
+    bounds.dedup();
+    let segments = bounds.windows(2).map(|bound| {
+        let (start, end) = (bound[0], bound[1]);
+        let covers = |s: usize, e: usize| s <= start && end <= e;
+        let segment_style = if matches.iter().any(|&(s, e)| covers(s, e)) {
+            match_style
+        } else {
+            colors
+                .iter()
+                .filter(|&&(s, e, _)| covers(s, e))
+                .fold(style, |style, &(_, _, color)| style.patch(color))
+        };
+        (&text[start..end], segment_style)
+    });
+
+    let mut skip = skip;
+    let spans: Vec<Span> = segments
+        .filter_map(|(segment, style)| {
+            let len = segment.chars().count();
+            if skip >= len {
+                skip -= len;
+                return None;
+            }
+            let visible: String = segment.chars().skip(skip).collect();
+            skip = 0;
+            Some(Span::styled(visible, style))
+        })
+        .collect();
+    Line::from(spans)
+}
+
+// Breaks a line into rows of at most `width` characters, keeping span styles.
+fn wrap_line(line: Line<'static>, width: usize) -> Vec<Line<'static>> {
+    if width == 0 {
+        return vec![line];
+    }
+    let mut lines = Vec::new();
+    let mut current: Vec<Span<'static>> = Vec::new();
+    let mut used = 0;
+    for span in line.spans {
+        let content = span.content.to_string();
+        let mut rest = content.as_str();
+        while !rest.is_empty() {
+            if used == width {
+                lines.push(Line::from(std::mem::take(&mut current)));
+                used = 0;
+            }
+            let split = rest
+                .char_indices()
+                .nth(width - used)
+                .map_or(rest.len(), |(i, _)| i);
+            let (head, tail) = rest.split_at(split);
+            used += head.chars().count();
+            current.push(Span::styled(head.to_string(), span.style));
+            rest = tail;
+        }
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(Line::from(current));
+    }
+    lines
+}
 
+// Merged streams tag each line with its service; each service gets its own colour.
+fn source_style(app: &App, tag: Option<&str>) -> Option<Style> {
+    let tag = tag?;
+    // ECS service events are only ever tagged with the service
+    let i = app.source_tags.iter().position(|t| t == tag).or_else(|| {
+        (app.service.len() > 1)
+            .then(|| app.service.iter().position(|s| s == tag))
+            .flatten()
+    })?;
+    let theme = &app.theme;
+    let palette = [
+        theme.cyan,
+        theme.purple,
+        theme.pink,
+        theme.orange,
+        theme.green,
+        theme.yellow,
+    ];
+    Some(Style::default().fg(palette[i % palette.len()]))
+}
+
+// " [eu-west-1]" after a cluster or service, so identically named ones in
+// different regions can't be mixed up.
+fn region_badge(arn: Option<&str>, style: Style, theme: &Theme) -> Option<Span<'static>> {
+    let region = arn.and_then(arn_region)?;
+    Some(Span::styled(
+        format!(" [{}]", region),
+        style.fg(theme.comment),
+    ))
+}
+
+// Draws a one-line text input above the event list and returns the space left
+// for the list.
+fn input_bar(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    title: &str,
+    input: &str,
+    color: Color,
+) -> Rect {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .margin(2)
-        .constraints([
-            Constraint::Percentage(10),
-            Constraint::Percentage(80),
-            Constraint::Percentage(10),
-        ])
-        .split(frame.area());
-
-    let title_block = Block::default()
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(area);
+    let block = Block::default()
+        .title(title)
         .borders(Borders::ALL)
-        .style(Style::default());
+        .style(Style::default().fg(color));
+    let bar = Paragraph::new(Line::from(vec![
+        Span::styled(input, Style::default().fg(theme.foreground)),
+        Span::styled("█", Style::default().fg(theme.foreground)),
+    ]))
+    .block(block);
+    frame.render_widget(bar, chunks[0]);
+    chunks[1]
+}
 
-    let title = Paragraph::new(Text::styled(
-        "LazyLogger",
-        Style::default()
-            .bg(Theme::default().background)
-            .fg(Theme::default().green),
-    ))
-    .block(title_block);
+// The list widget decides its own offset while rendering, so the scrollbar is
+// derived from that offset afterwards rather than from the selected index.
+fn sync_scrollbar(state: &mut ScrollbarState, list_state: &ListState, len: usize, area: Rect) {
+    let visible_rows = usize::from(area.height.saturating_sub(2));
+    *state = state
+        .content_length(len.saturating_sub(visible_rows) + 1)
+        .viewport_content_length(visible_rows)
+        .position(list_state.offset());
+}
 
-    frame.render_widget(title, chunks[0]);
+// The ECS service events for the selected services, either as the top pane of
+// the split view or in place of the logs.
+fn render_ecs_events(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = app.theme;
+    app.ecs_event_box.area = area;
+    let title = match app.event_source {
+        EventSource::EcsEvents => " ECS Service Events - (v) to show logs ",
+        EventSource::Logs => " ECS Service Events - (Tab) to switch pane - (s) to close ",
+    };
+    let mut block = Block::default().title(title).borders(Borders::ALL);
+    if app.ecs_events_active() {
+        block = block.style(Style::default().fg(theme.green));
+    }
+    if app.ecs_refresh_paused() {
+        block = block.title(
+            Line::from(Span::styled(
+                " ⏸ refresh paused while scrolling ",
+                Style::default().fg(theme.yellow),
+            ))
+            .right_aligned(),
+        );
+    }
+
+    if let Some(error) = &app.ecs_events_error {
+        let error_block = Paragraph::new(error.as_str())
+            .style(Style::default().fg(theme.red))
+            .wrap(Wrap { trim: false })
+            .block(block);
+        frame.render_widget(error_block, area);
+        return;
+    }
+    if app.ecs_events.items.is_empty() {
+        let message = if app.ecs_events_refreshed.is_some() {
+            "No service events"
+        } else {
+            "Loading ECS Service Events..."
+        };
+        let empty_block = Paragraph::new(message)
+            .style(Style::default().fg(theme.comment))
+            .block(block);
+        frame.render_widget(empty_block, area);
+        return;
+    }
+
+    let now = Utc::now();
+    let items: Vec<ListItem> = app
+        .ecs_events
+        .items
+        .iter()
+        .map(|event| {
+            ListItem::new(Line::from(Span::styled(
+                format!(
+                    "[{}] {}",
+                    app.format_timestamp(event.timestamp, now),
+                    event.text()
+                ),
+                source_style(app, event.tag.as_deref())
+                    .unwrap_or(Style::default().fg(theme.foreground)),
+            )))
+        })
+        .collect();
+    let list = List::new(items).block(block).highlight_symbol(">> ");
+    frame.render_stateful_widget(list, area, &mut app.ecs_events.state);
+
+    let scrollbar = Scrollbar::default()
+        .orientation(ratatui::widgets::ScrollbarOrientation::VerticalRight)
+        .style(Style::default().bg(theme.selection));
+    sync_scrollbar(
+        &mut app.ecs_event_box.vertical_scroll_state,
+        &app.ecs_events.state,
+        app.ecs_events.items.len(),
+        area,
+    );
+    frame.render_stateful_widget(
+        scrollbar,
+        area,
+        &mut app.ecs_event_box.vertical_scroll_state,
+    );
+}
+
+// Desired/running count and the CPU/memory each tailed service runs with,
+// shown beside the logs with (i).
+fn render_service_info(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme;
+    let mut lines = Vec::new();
+    for source in &app.log_sources {
+        let Some(info) = &source.info else {
+            continue;
+        };
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            source.service.clone(),
+            Style::default().fg(theme.cyan).add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(format!(
+            "tasks: {} running / {} desired",
+            info.running_count, info.desired_count
+        )));
+        if info.task_cpu.is_some() || info.task_memory.is_some() {
+            lines.push(Line::from(format!(
+                "task: cpu {} / memory {} MiB",
+                info.task_cpu.as_deref().unwrap_or("-"),
+                info.task_memory.as_deref().unwrap_or("-")
+            )));
+        }
+        for container in &info.containers {
+            let memory = match (container.memory, container.memory_reservation) {
+                (Some(limit), Some(reserved)) => format!("{} MiB (reserved {})", limit, reserved),
+                (Some(limit), None) => format!("{} MiB", limit),
+                (None, Some(reserved)) => format!("reserved {} MiB", reserved),
+                (None, None) => "-".to_string(),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{}: ", container.name),
+                    Style::default().fg(theme.yellow),
+                ),
+                Span::raw(format!("cpu {} / memory {}", container.cpu, memory)),
+            ]));
+        }
+    }
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No ECS service info for these logs",
+            Style::default().fg(theme.comment),
+        )));
+    }
+    let info = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .style(Style::default().fg(theme.foreground))
+        .block(
+            Block::default()
+                .title(" Service Info - (i) to hide ")
+                .borders(Borders::ALL)
+                .style(Style::default().fg(theme.purple)),
+        );
+    frame.render_widget(info, area);
+}
+
+// The CloudWatch log list with its search bar, rate sparkline and side panes.
+fn render_logs(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = app.theme;
+    let keys = app.keymap;
 
     let mut event_block = Block::default()
-        .title(" Service Events - (e) to focus ")
+        .title(format!(
+            " Service Events - ({}) to focus ",
+            key_name(keys.focus)
+        ))
         .borders(Borders::ALL);
 
+    let fetch_state = app.log_fetch_state();
+    // focused, the block's colour tells fetching, loaded and empty apart
+    let focused_color = match fetch_state {
+        LogFetchState::Fetching => theme.yellow,
+        LogFetchState::Empty => theme.comment,
+        LogFetchState::Idle | LogFetchState::Loaded => theme.green,
+    };
     if app.viewing_logs
         && !app.service_events.items.is_empty()
         && matches!(app.current_screen, CurrentScreen::Main)
     {
         event_block = Block::default()
-            .title(" Service Events - (e) to unfocus - (r) to refresh - (Enter) for details ")
+            .title(format!(
+                " Service Events - ({}) to unfocus - ({}) to refresh - (/) to search - ({}) for details ",
+                key_name(keys.focus),
+                key_name(keys.refresh),
+                key_name(keys.enter)
+            ))
             .borders(Borders::ALL)
-            .style(Style::default().fg(Theme::default().green));
+            .style(Style::default().fg(focused_color));
     } else if app.viewing_logs
         && app.service_events.items.is_empty()
         && matches!(app.current_screen, CurrentScreen::Main)
     {
         event_block = Block::default()
-            .title(" Service Events - (e) to unfocus ")
+            .title(format!(
+                " Service Events - ({}) to unfocus ",
+                key_name(keys.focus)
+            ))
             .borders(Borders::ALL)
-            .style(Style::default().fg(Theme::default().green));
+            .style(Style::default().fg(focused_color));
+    }
+
+    if let LogFetchState::Fetching = fetch_state {
+        event_block = event_block.title(
+            Line::from(Span::styled(
+                " ⟳ fetching logs… ",
+                Style::default().fg(theme.yellow),
+            ))
+            .right_aligned(),
+        );
+    }
+
+    if app.logs_truncated && !app.service_events.items.is_empty() {
+        event_block = event_block.title(
+            Line::from(Span::styled(
+                " ⚠ more logs available (m to load) ",
+                Style::default().fg(theme.yellow),
+            ))
+            .right_aligned(),
+        );
+    }
+
+    if let Some(log_group) = &app.direct_log_group {
+        event_block = event_block.title(Line::from(Span::styled(
+            format!(" log group: {} ", log_group),
+            Style::default().fg(theme.cyan),
+        )));
+    }
+
+    if let Some((_, log_stream)) = &app.log_stream {
+        event_block = event_block.title(Line::from(Span::styled(
+            format!(" stream: {} (l to change) ", log_stream),
+            Style::default().fg(theme.yellow),
+        )));
     }
 
-    let event_items: Vec<ListItem> = app
+    if let Some(prefix) = &app.stream_prefix {
+        event_block = event_block.title(Line::from(Span::styled(
+            format!(" stream prefix: {} ", prefix),
+            Style::default().fg(theme.yellow),
+        )));
+    }
+
+    if app.columns_shown() {
+        event_block = event_block.title(Line::from(Span::styled(
+            format!(" columns: {} (J for raw) ", app.json_columns.join(", ")),
+            Style::default().fg(theme.cyan),
+        )));
+    }
+
+    if app.hide_health_checks {
+        event_block = event_block.title(Line::from(Span::styled(
+            " health checks hidden (H) ",
+            Style::default().fg(theme.comment),
+        )));
+    }
+
+    if app.time_window.is_set() {
+        event_block = event_block.title(Line::from(Span::styled(
+            format!(" window: {} ", app.time_window.label()),
+            Style::default().fg(theme.purple),
+        )));
+    }
+
+    if let Some(pattern) = &app.filter_pattern {
+        event_block = event_block.title(Line::from(Span::styled(
+            format!(" filter pattern: {} ", pattern),
+            Style::default().fg(theme.pink),
+        )));
+    }
+
+    let mut event_area = area;
+    if app.split_view {
+        let split_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(35), Constraint::Min(0)])
+            .split(event_area);
+        render_ecs_events(frame, app, split_chunks[0]);
+        event_area = split_chunks[1];
+    }
+    if app.show_service_info && !app.log_sources.is_empty() {
+        let info_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(0), Constraint::Length(44)])
+            .split(event_area);
+        render_service_info(frame, app, info_chunks[1]);
+        event_area = info_chunks[0];
+    }
+    if app.prefix_mode {
+        event_area = input_bar(
+            frame,
+            &theme,
+            event_area,
+            " Log stream prefix - (Enter) apply / (Esc) cancel, empty for all streams ",
+            &app.prefix_input,
+            theme.yellow,
+        );
+    } else if app.window_mode {
+        event_area = input_bar(
+            frame,
+            &theme,
+            event_area,
+            " Time window - 15m, 2h..1h or 2024-05-01 10:00..2024-05-01 11:00 - (Tab) presets / (Enter) apply / (Esc) cancel, empty to tail ",
+            &app.window_input,
+            theme.purple,
+        );
+    } else if app.pattern_mode {
+        event_area = input_bar(
+            frame,
+            &theme,
+            event_area,
+            " CloudWatch filter pattern, e.g. ERROR or { $.level = \"error\" } - (Enter) apply / (Esc) cancel, empty for everything ",
+            &app.pattern_input,
+            theme.pink,
+        );
+    } else if app.search_mode || app.search_active() {
+        let search_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(event_area);
+        event_area = search_chunks[1];
+
+        let mut search_line = vec![
+            Span::styled("/", Style::default().fg(theme.comment)),
+            Span::styled(
+                app.search_query.as_str(),
+                Style::default().fg(theme.foreground),
+            ),
+        ];
+        if app.search_mode {
+            search_line.push(Span::styled("█", Style::default().fg(theme.foreground)));
+        }
+        if app.search_active() && app.search_error.is_none() {
+            let total = app.filtered_indices.len();
+            let position = app
+                .service_events
+                .state
+                .selected()
+                .and_then(|selected| app.filtered_indices.iter().position(|&i| i == selected));
+            search_line.push(match (total, position) {
+                (0, _) => Span::styled("  no matches", Style::default().fg(theme.red)),
+                (_, Some(position)) => Span::styled(
+                    format!("  match {} of {}", position + 1, total),
+                    Style::default().fg(theme.comment),
+                ),
+                (_, None) => Span::styled(
+                    format!("  {} matches", total),
+                    Style::default().fg(theme.comment),
+                ),
+            });
+        }
+        if let Some(search_error) = &app.search_error {
+            search_line.push(Span::styled(
+                format!("  ⚠ {}", search_error),
+                Style::default().fg(theme.red),
+            ));
+        }
+        let search_block = Block::default()
+            .title(format!(
+                " Search - (R) regex: {} - (n/N) next/previous match ",
+                if app.use_regex { "on" } else { "off" }
+            ))
+            .borders(Borders::ALL)
+            .style(Style::default().fg(theme.yellow));
+        let search_bar = Paragraph::new(Line::from(search_line)).block(search_block);
+        frame.render_widget(search_bar, search_chunks[0]);
+    }
+
+    // events per minute over the loaded window, so a burst stands out before
+    // reading any lines
+    if !app.service_events.items.is_empty() && event_area.height > 12 {
+        let rate_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(event_area);
+        let timestamps: Vec<i64> = app
+            .service_events
+            .items
+            .iter()
+            .map(|event| event.timestamp)
+            .collect();
+        let width = usize::from(rate_chunks[0].width.saturating_sub(2));
+        let (rate, minutes) = event_rate(&timestamps, width);
+        let peak = rate.iter().max().copied().unwrap_or(0);
+        let sparkline = Sparkline::default()
+            .block(
+                Block::default()
+                    .title(format!(
+                        " events per {} - peak {} ",
+                        if minutes == 1 {
+                            "minute".to_string()
+                        } else {
+                            format!("{} minutes", minutes)
+                        },
+                        peak
+                    ))
+                    .padding(Padding::horizontal(1)),
+            )
+            .data(&rate)
+            .style(Style::default().fg(theme.cyan));
+        frame.render_widget(sparkline, rate_chunks[0]);
+        event_area = rate_chunks[1];
+    }
+
+    app.event_box.area = event_area;
+    let visible_indices = app.visible_event_indices();
+    // Position among the lines actually shown, so it follows an active search.
+    let position = app
         .service_events
-        .items
+        .state
+        .selected()
+        .and_then(|selected| visible_indices.iter().position(|&i| i == selected));
+    if let Some(position) = position {
+        event_block = event_block.title_bottom(
+            Line::from(format!(
+                " line {} of {} ",
+                position + 1,
+                visible_indices.len()
+            ))
+            .right_aligned(),
+        );
+    }
+
+    let event_style = Style::default().fg(theme.foreground);
+    let match_style = Style::default().fg(theme.background).bg(theme.yellow);
+    // Timestamps are rendered per frame so relative ages stay current; search
+    // highlights are computed against the displayed text.
+    let now = Utc::now();
+    let mark = app.time_mark.map(|mark| mark.timestamp_millis());
+    // Each visible event with the style it's drawn in and its ANSI colours;
+    // lines from before the time mark are dimmed.
+    let column_messages = app.column_messages(&visible_indices);
+    let display_events: Vec<(String, Style, AnsiRanges)> = visible_indices
         .iter()
-        .map(|item| {
-            ListItem::new(Line::from(Span::styled(
-                item,
-                Style::default().fg(Theme::default().foreground),
-            )))
+        .zip(column_messages)
+        .map(|(&i, column_message)| {
+            let event = &app.service_events.items[i];
+            let dimmed = mark.is_some_and(|mark| event.timestamp < mark);
+            let style = if dimmed {
+                Style::default().fg(theme.comment)
+            } else if event.is_stderr {
+                Style::default().fg(theme.orange)
+            } else {
+                source_style(app, event.tag.as_deref()).unwrap_or(event_style)
+            };
+            let columns = column_message.is_some();
+            let text = app.display_format.render(
+                &app.format_timestamp(event.timestamp, now),
+                &column_message.unwrap_or_else(|| event.text()),
+            );
+            // the colours are relative to the message, which ends the text
+            // unless the format leaves it out or it's drawn as columns
+            let colors = if !dimmed && !columns && text.ends_with(&event.message) {
+                let offset = text.len() - event.message.len();
+                event
+                    .colors
+                    .iter()
+                    .map(|&(start, end, color)| (start + offset, end + offset, color))
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            (text, style, colors)
         })
         .collect();
+    let event_items: Vec<ListItem> = if app.wrap_logs {
+        // one list row per visual line, remembering which event each row belongs to
+        // (borders and the ">> " highlight symbol take up 5 columns)
+        let width = usize::from(event_area.width.saturating_sub(5));
+        let mut rows = Vec::new();
+        app.wrapped_rows.clear();
+        for (&i, (item, style, colors)) in visible_indices.iter().zip(&display_events) {
+            let line = highlighted_line(
+                item,
+                &app.search_match_ranges(item),
+                colors,
+                0,
+                *style,
+                match_style,
+            );
+            for row in wrap_line(line, width) {
+                rows.push(ListItem::new(row));
+                app.wrapped_rows.push(i);
+            }
+        }
+        let selected_event = app.service_events.state.selected();
+        let selected_row = app
+            .wrap_state
+            .selected()
+            .and_then(|row| app.wrapped_rows.get(row))
+            .copied();
+        if selected_row != selected_event {
+            let row = selected_event.and_then(|i| app.wrapped_rows.iter().position(|&r| r == i));
+            app.wrap_state.select(row);
+        }
+        rows
+    } else {
+        display_events
+            .iter()
+            .map(|(item, style, colors)| {
+                ListItem::new(highlighted_line(
+                    item,
+                    &app.search_match_ranges(item),
+                    colors,
+                    app.event_box.horizontal_scroll,
+                    *style,
+                    match_style,
+                ))
+            })
+            .collect()
+    };
 
     let event_list = List::new(event_items)
         .block(event_block.clone())
@@ -95,9 +691,21 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
 
     let event_list_scrollbar = Scrollbar::default()
         .orientation(ratatui::widgets::ScrollbarOrientation::VerticalRight)
-        .style(Style::default().bg(Theme::default().selection));
+        .style(Style::default().bg(theme.selection));
 
-    let selected = app.service_events.state.selected().unwrap_or(0);
+    let selected_position = app
+        .service_events
+        .state
+        .selected()
+        .and_then(|selected| visible_indices.iter().position(|&i| i == selected));
+    let (row_count, selected) = if app.wrap_logs {
+        (
+            app.wrapped_rows.len(),
+            app.wrap_state.selected().unwrap_or(0),
+        )
+    } else {
+        (visible_indices.len(), selected_position.unwrap_or(0))
+    };
 
     if selected < app.event_box.vertical_scroll {
         app.event_box.vertical_scroll = selected;
@@ -108,72 +716,182 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
     app.event_box.vertical_scroll_state = app
         .event_box
         .vertical_scroll_state
+        .content_length(row_count)
         .position(app.event_box.vertical_scroll);
 
-    if !app.profile.is_empty()
-        && !app.cluster.is_empty()
-        && !app.service.is_empty()
+    if let Some(log_error) = &app.log_error {
+        let error_block = Paragraph::new(log_error.as_str())
+            .style(Style::default().fg(theme.red))
+            .wrap(Wrap { trim: false })
+            .block(event_block);
+        frame.render_widget(error_block, event_area);
+    } else if app.filter_active() && !app.service_events.items.is_empty() && event_list.is_empty() {
+        let message = if app.search_active() {
+            "No log events match the search"
+        } else {
+            "Every log event is a health check (H to show them)"
+        };
+        let no_matches_block = Paragraph::new(message)
+            .style(Style::default().fg(theme.comment))
+            .block(event_block);
+        frame.render_widget(no_matches_block, event_area);
+    } else if app.has_log_target() && app.logs_loaded && event_list.is_empty() {
+        let empty_block = Paragraph::new("No log events in selected range")
+            .style(Style::default().fg(theme.comment))
+            .block(event_block);
+        frame.render_widget(empty_block, event_area);
+    } else if app.has_log_target()
         && event_list.is_empty()
         && matches!(app.current_screen, CurrentScreen::Main)
     {
         let loading_block = Paragraph::new("Loading Service Event Logs...")
-            .style(Style::default().fg(Theme::default().yellow))
+            .style(Style::default().fg(theme.yellow))
             .block(event_block);
-        frame.render_widget(loading_block, chunks[1]);
+        frame.render_widget(loading_block, event_area);
     } else if event_list.is_empty() {
         let idle_block = Paragraph::new("Configure Data Source to View Logs").block(event_block);
-        frame.render_widget(idle_block, chunks[1]);
+        frame.render_widget(idle_block, event_area);
+    } else if app.wrap_logs {
+        frame.render_stateful_widget(event_list, event_area, &mut app.wrap_state);
+        frame.render_stateful_widget(
+            event_list_scrollbar,
+            event_area,
+            &mut app.event_box.vertical_scroll_state,
+        );
+    } else if app.filter_active() {
+        app.filtered_state.select(selected_position);
+        frame.render_stateful_widget(event_list, event_area, &mut app.filtered_state);
+        frame.render_stateful_widget(
+            event_list_scrollbar,
+            event_area,
+            &mut app.event_box.vertical_scroll_state,
+        );
     } else {
-        frame.render_stateful_widget(event_list, chunks[1], &mut app.service_events.state);
+        frame.render_stateful_widget(event_list, event_area, &mut app.service_events.state);
         frame.render_stateful_widget(
             event_list_scrollbar,
-            chunks[1],
+            event_area,
             &mut app.event_box.vertical_scroll_state,
         );
     }
+}
+
+pub fn ui(frame: &mut Frame, app: &mut App) {
+    let theme = app.theme;
+    let background = Block::default().style(Style::default().bg(theme.background));
+    frame.render_widget(background, frame.area());
+
+    let area = frame.area();
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+        let message = Paragraph::new(format!(
+            "Terminal too small ({}x{}), LazyLogger needs at least {}x{}",
+            area.width, area.height, MIN_WIDTH, MIN_HEIGHT
+        ))
+        .style(Style::default().fg(theme.yellow))
+        .wrap(Wrap { trim: true });
+        frame.render_widget(message, area);
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Percentage(10),
+            Constraint::Percentage(80),
+            Constraint::Percentage(10),
+        ])
+        .split(frame.area());
+
+    let mut title_block = Block::default()
+        .borders(Borders::ALL)
+        .style(Style::default());
+    if let Some(breadcrumb) = app.breadcrumb() {
+        title_block = title_block.title(Span::styled(
+            format!(" {} ", breadcrumb),
+            Style::default().fg(theme.cyan).add_modifier(Modifier::BOLD),
+        ));
+    }
+    if let Some(identity) = &app.identity {
+        title_block = title_block.title(
+            Line::from(Span::styled(
+                format!(" account {} · {} ", identity.account, identity.arn),
+                Style::default().fg(theme.yellow),
+            ))
+            .right_aligned(),
+        );
+    }
+
+    let title = if app.is_prod() {
+        Paragraph::new(Text::styled(
+            format!("LazyLogger  ⚠ PRODUCTION — profile {} ⚠", app.profile),
+            Style::default()
+                .bg(theme.red)
+                .fg(theme.background)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .block(title_block.style(Style::default().bg(theme.red)))
+    } else {
+        Paragraph::new(Text::styled(
+            "LazyLogger",
+            Style::default().bg(theme.background).fg(theme.green),
+        ))
+        .block(title_block)
+    };
+
+    frame.render_widget(title, chunks[0]);
+
+    let keys = app.keymap;
+
+    // `v` swaps the application logs for the services' ECS events
+    match app.event_source {
+        EventSource::Logs => render_logs(frame, app, chunks[1]),
+        EventSource::EcsEvents => render_ecs_events(frame, app, chunks[1]),
+    }
 
     let current_navigation_text = vec![
         // The first half of the text
         match app.current_screen {
-            CurrentScreen::Main => {
-                Span::styled("Logging Mode", Style::default().fg(Theme::default().green))
+            CurrentScreen::Main => Span::styled("Logging Mode", Style::default().fg(theme.green)),
+            CurrentScreen::SettingConfig => {
+                Span::styled("Set Data Source", Style::default().fg(theme.yellow))
             }
-            CurrentScreen::SettingConfig => Span::styled(
-                "Set Data Source",
-                Style::default().fg(Theme::default().yellow),
-            ),
             CurrentScreen::LogDetails => {
-                Span::styled("Log Details", Style::default().fg(Theme::default().green))
+                Span::styled("Log Details", Style::default().fg(theme.green))
+            }
+            CurrentScreen::Deployments => {
+                Span::styled("Deployments", Style::default().fg(theme.green))
             }
-            CurrentScreen::Exiting => {
-                Span::styled("Exiting", Style::default().fg(Theme::default().red))
+            CurrentScreen::StoppedTasks => {
+                Span::styled("Stopped Tasks", Style::default().fg(theme.green))
             }
+            CurrentScreen::LogStreams => {
+                Span::styled("Log Streams", Style::default().fg(theme.green))
+            }
+            CurrentScreen::Exiting => Span::styled("Exiting", Style::default().fg(theme.red)),
         }
         .to_owned(),
         // A white divider bar to separate the two sections
-        Span::styled(" | ", Style::default().fg(Theme::default().foreground)),
+        Span::styled(" | ", Style::default().fg(theme.foreground)),
         // The final section of the text, with hints on what the user is editing
         {
             if let Some(setting_config) = &app.setting_config {
                 match setting_config {
-                    SettingConfig::Profile => Span::styled(
-                        "Setting AWS Profile",
-                        Style::default().fg(Theme::default().green),
-                    ),
-                    SettingConfig::Cluster => Span::styled(
-                        "Setting ECS Cluster",
-                        Style::default().fg(Theme::default().green),
-                    ),
-                    SettingConfig::Service => Span::styled(
-                        "Setting ECS Service",
-                        Style::default().fg(Theme::default().green),
-                    ),
+                    SettingConfig::Profile => {
+                        Span::styled("Setting AWS Profile", Style::default().fg(theme.green))
+                    }
+                    SettingConfig::Cluster => {
+                        Span::styled("Setting ECS Cluster", Style::default().fg(theme.green))
+                    }
+                    SettingConfig::Service => {
+                        Span::styled("Setting ECS Service", Style::default().fg(theme.green))
+                    }
+                    SettingConfig::Favorites => {
+                        Span::styled("Opening Favorite", Style::default().fg(theme.green))
+                    }
                 }
             } else {
-                Span::styled(
-                    "Not Setting Anything",
-                    Style::default().fg(Theme::default().comment),
-                )
+                Span::styled("Not Setting Anything", Style::default().fg(theme.comment))
             }
         },
     ];
@@ -184,20 +902,34 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
     let current_keys_hint = {
         match app.current_screen {
             CurrentScreen::Main => Span::styled(
-                "(q) to quit / (c) to config data source",
-                Style::default().fg(Theme::default().red),
+                format!(
+                    "({}) to quit / ({}) to config data source / (S) change service / (d) deployments / (x) stopped tasks / (i) service info / (l) log streams / (b) bookmark",
+                    key_name(keys.quit),
+                    key_name(keys.config)
+                ),
+                Style::default().fg(theme.red),
             ),
             CurrentScreen::SettingConfig => Span::styled(
-                "(ESC) to cancel/(Tab) to switch boxes/enter to complete",
-                Style::default().fg(Theme::default().red),
+                format!(
+                    "(ESC) to cancel/(Tab/Shift-Tab) to switch boxes/(Backspace) back/({}) to reload/enter to complete",
+                    key_name(keys.refresh)
+                ),
+                Style::default().fg(theme.red),
             ),
-            CurrentScreen::LogDetails => Span::styled(
-                "(ESC) to go back",
-                Style::default().fg(Theme::default().red),
+            CurrentScreen::LogDetails
+            | CurrentScreen::Deployments
+            | CurrentScreen::StoppedTasks
+            | CurrentScreen::LogStreams => Span::styled(
+                "(ESC) to go back".to_string(),
+                Style::default().fg(theme.red),
             ),
             CurrentScreen::Exiting => Span::styled(
-                "(q) to quit / (c) to config data source",
-                Style::default().fg(Theme::default().red),
+                format!(
+                    "({}) to quit / ({}) to config data source",
+                    key_name(keys.quit),
+                    key_name(keys.config)
+                ),
+                Style::default().fg(theme.red),
             ),
         }
     };
@@ -205,36 +937,122 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
     let key_notes_footer =
         Paragraph::new(Line::from(current_keys_hint)).block(Block::default().borders(Borders::ALL));
 
+    let status_text = match (&app.status_message, app.last_refreshed) {
+        _ if app.is_offline() => Span::styled(
+            format!(
+                "offline — retrying in {}s ({} failed attempts)",
+                app.retry_in().as_secs(),
+                app.network_failures
+            ),
+            Style::default().fg(theme.red),
+        ),
+        (Some(message), _) => Span::styled(message.clone(), Style::default().fg(theme.yellow)),
+        (None, Some(last_refreshed)) if app.logs_loaded => Span::styled(
+            format!(
+                "{} events • last refreshed {} • (t) {} time • (f) {}",
+                app.service_events.items.len(),
+                last_refreshed.format("%H:%M:%S"),
+                app.timestamp_mode.label(),
+                app.display_format.label()
+            ),
+            Style::default().fg(theme.foreground),
+        ),
+        _ => Span::styled("No events loaded", Style::default().fg(theme.comment)),
+    };
+
+    let status_footer =
+        Paragraph::new(Line::from(status_text)).block(Block::default().borders(Borders::ALL));
+
     let footer_chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .constraints([
+            Constraint::Percentage(30),
+            Constraint::Percentage(45),
+            Constraint::Percentage(25),
+        ])
         .split(chunks[2]);
 
     frame.render_widget(mode_footer, footer_chunks[0]);
     frame.render_widget(key_notes_footer, footer_chunks[1]);
+    frame.render_widget(status_footer, footer_chunks[2]);
     if let Some(setting_config) = &app.setting_config {
+        let narrow = frame.area().width < NARROW_WIDTH;
         let popup_block = Block::default()
-            .title("Setting Data Source")
+            .title(if narrow {
+                "Setting Data Source - (Tab) next box"
+            } else {
+                "Setting Data Source"
+            })
             .borders(Borders::NONE)
-            .style(Style::default().bg(Theme::default().selection));
+            .style(Style::default().bg(theme.selection));
 
-        let area = centered_rect(60, 25, frame.area());
+        let popup = app.popup;
+        let area = centered_rect(
+            if narrow {
+                100
+            } else {
+                popup.width.clamp(10, 100)
+            },
+            if app.bookmarks.is_empty() {
+                popup.height.clamp(10, 100)
+            } else {
+                popup.height.saturating_add(15).clamp(10, 100)
+            },
+            frame.area(),
+        );
         frame.render_widget(popup_block, area);
 
-        let popup_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .margin(1)
-            .constraints([
-                Constraint::Percentage(30),
-                Constraint::Percentage(30),
-                Constraint::Percentage(30),
-            ])
+        // favorites get their own row under the pickers once any exist
+        let favorite_rows = if app.bookmarks.is_empty() {
+            0
+        } else {
+            app.bookmarks.len().min(5) as u16 + 2
+        };
+        let popup_rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(favorite_rows)])
             .split(area);
+        let popup_chunks = if narrow {
+            // stack the pickers: only the active one gets any space
+            let mut chunks = vec![Rect::default(); 3];
+            let shown = match setting_config {
+                SettingConfig::Profile => 0,
+                SettingConfig::Cluster => 1,
+                SettingConfig::Service | SettingConfig::Favorites => 2,
+            };
+            chunks[shown] = popup_rows[0].inner(Margin::new(1, 1));
+            chunks
+        } else {
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .margin(1)
+                .constraints(popup.columns.map(Constraint::Percentage))
+                .split(popup_rows[0])
+                .to_vec()
+        };
+        app.profile_box.area = popup_chunks[0];
+        app.cluster_box.area = popup_chunks[1];
+        app.service_box.area = popup_chunks[2];
+        app.favorite_box.area = popup_rows[1].inner(Margin::new(1, 0));
         let mut profile_block = Block::default().title("AWS Profile").borders(Borders::ALL);
         let mut cluster_block = Block::default().title("ECS Cluster").borders(Borders::ALL);
-        let mut service_block = Block::default().title("ECS Service").borders(Borders::ALL);
+        let mut service_block = Block::default()
+            .title(format!(
+                "ECS Service - (f) {} - (s) by {}",
+                app.launch_type_filter.label(),
+                if app.sort_services_by_health {
+                    "health"
+                } else {
+                    "name"
+                }
+            ))
+            .borders(Borders::ALL);
+
+        let mut favorite_block = Block::default()
+            .title("★ Favorites - (b) on a service to add/remove")
+            .borders(Borders::ALL);
 
-        let active_style = Style::default().fg(Theme::default().green);
+        let active_style = Style::default().fg(theme.green);
 
         match setting_config {
             SettingConfig::Profile => {
@@ -246,6 +1064,9 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
             SettingConfig::Service => {
                 service_block = service_block.style(active_style);
             }
+            SettingConfig::Favorites => {
+                favorite_block = favorite_block.style(active_style);
+            }
         }
 
         let profile_items: Vec<ListItem> = app
@@ -256,92 +1077,120 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
                 if app.profile == *item {
                     ListItem::new(Line::from(Span::styled(
                         item,
-                        Style::default()
-                            .fg(Theme::default().background)
-                            .bg(Theme::default().green),
+                        Style::default().fg(theme.background).bg(theme.green),
                     )))
                 } else if Some(item) == app.profiles.selected() {
                     ListItem::new(Line::from(Span::styled(
                         item,
-                        Style::default()
-                            .fg(Theme::default().foreground)
-                            .bg(Theme::default().current_line),
+                        Style::default().fg(theme.foreground).bg(theme.current_line),
                     )))
                 } else {
                     ListItem::new(Line::from(Span::styled(
                         item,
-                        Style::default()
-                            .fg(Theme::default().foreground)
-                            .bg(Theme::default().selection),
+                        Style::default().fg(theme.foreground).bg(theme.selection),
                     )))
                 }
             })
             .collect();
 
         let profile_list = List::new(profile_items)
-            .block(profile_block)
+            .block(profile_block.clone())
             .highlight_symbol(">> ");
 
         let profile_list_scrollbar = Scrollbar::default()
             .orientation(ratatui::widgets::ScrollbarOrientation::VerticalRight)
-            .style(Style::default().bg(Theme::default().selection));
+            .style(Style::default().bg(theme.selection));
 
-        frame.render_stateful_widget(profile_list, popup_chunks[0], &mut app.profiles.state);
-        frame.render_stateful_widget(
-            profile_list_scrollbar,
-            popup_chunks[0],
-            &mut app.profile_box.vertical_scroll_state,
-        );
+        let profile_error = app
+            .picker_error
+            .as_ref()
+            .filter(|_| app.profiles.items.is_empty());
+        if app.profiles_loaded && app.profiles.items.is_empty() {
+            let empty_text = match &app.profile_filter {
+                Some(filter) => format!("No profiles match \"{}\"", filter),
+                None => {
+                    "No profiles found — check ~/.aws/credentials and ~/.aws/config".to_string()
+                }
+            };
+            let empty_block = Paragraph::new(empty_text)
+                .style(Style::default().bg(theme.selection).fg(theme.comment))
+                .wrap(Wrap { trim: false })
+                .block(profile_block);
+            frame.render_widget(empty_block, popup_chunks[0]);
+        } else if let Some(picker_error) = profile_error {
+            let error_block = Paragraph::new(picker_error.as_str())
+                .style(Style::default().bg(theme.selection).fg(theme.red))
+                .wrap(Wrap { trim: false })
+                .block(profile_block);
+            frame.render_widget(error_block, popup_chunks[0]);
+        } else {
+            frame.render_stateful_widget(profile_list, popup_chunks[0], &mut app.profiles.state);
+            sync_scrollbar(
+                &mut app.profile_box.vertical_scroll_state,
+                &app.profiles.state,
+                app.profiles.items.len(),
+                popup_chunks[0],
+            );
+            frame.render_stateful_widget(
+                profile_list_scrollbar,
+                popup_chunks[0],
+                &mut app.profile_box.vertical_scroll_state,
+            );
+        }
 
         let cluster_items: Vec<ListItem> = app
             .clusters
             .items
             .iter()
             .map(|item| {
-                if app.cluster == *item {
-                    ListItem::new(Line::from(Span::styled(
-                        item,
-                        Style::default()
-                            .fg(Theme::default().background)
-                            .bg(Theme::default().green),
-                    )))
+                let style = if app.cluster == *item {
+                    Style::default().fg(theme.background).bg(theme.green)
                 } else if Some(item) == app.clusters.selected() {
-                    ListItem::new(Line::from(Span::styled(
-                        item,
-                        Style::default()
-                            .fg(Theme::default().foreground)
-                            .bg(Theme::default().current_line),
-                    )))
+                    Style::default().fg(theme.foreground).bg(theme.current_line)
                 } else {
-                    ListItem::new(Line::from(Span::styled(
-                        item,
-                        Style::default()
-                            .fg(Theme::default().foreground)
-                            .bg(Theme::default().selection),
-                    )))
-                }
+                    Style::default().fg(theme.foreground).bg(theme.selection)
+                };
+                let mut spans = vec![Span::styled(item, style)];
+                spans.extend(region_badge(
+                    app.cluster_arns.get(item).map(String::as_str),
+                    style,
+                    &theme,
+                ));
+                ListItem::new(Line::from(spans))
             })
             .collect();
 
         let cluster_list_scrollbar = Scrollbar::default()
             .orientation(ratatui::widgets::ScrollbarOrientation::VerticalRight)
-            .style(Style::default().bg(Theme::default().selection));
+            .style(Style::default().bg(theme.selection));
 
         let cluster_list = List::new(cluster_items)
             .block(cluster_block.clone())
             .highlight_symbol(">> ");
 
-        if !app.profile.is_empty() && cluster_list.is_empty() {
+        if let Some(picker_error) = app
+            .picker_error
+            .as_ref()
+            .filter(|_| !app.profile.is_empty() && cluster_list.is_empty())
+        {
+            let error_block = Paragraph::new(picker_error.as_str())
+                .style(Style::default().bg(theme.selection).fg(theme.red))
+                .wrap(Wrap { trim: false })
+                .block(cluster_block);
+            frame.render_widget(error_block, popup_chunks[1]);
+        } else if !app.profile.is_empty() && cluster_list.is_empty() {
             let loading_block = Paragraph::new("Loading Clusters...")
-                .style(
-                    Style::default()
-                        .bg(Theme::default().selection)
-                        .fg(Theme::default().yellow),
-                )
+                .style(Style::default().bg(theme.selection).fg(theme.yellow))
                 .block(cluster_block);
             frame.render_widget(loading_block, popup_chunks[1]);
         } else {
             frame.render_stateful_widget(cluster_list, popup_chunks[1], &mut app.clusters.state);
+            sync_scrollbar(
+                &mut app.cluster_box.vertical_scroll_state,
+                &app.clusters.state,
+                app.clusters.items.len(),
+                popup_chunks[1],
+            );
             frame.render_stateful_widget(
                 cluster_list_scrollbar,
                 popup_chunks[1],
@@ -354,28 +1203,30 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
             .items
             .iter()
             .map(|item| {
-                if app.service == *item {
-                    ListItem::new(Line::from(Span::styled(
-                        item,
-                        Style::default()
-                            .fg(Theme::default().background)
-                            .bg(Theme::default().green),
-                    )))
+                let style = if app.service.contains(item) {
+                    Style::default().fg(theme.background).bg(theme.green)
                 } else if Some(item) == app.services.selected() {
-                    ListItem::new(Line::from(Span::styled(
-                        item,
-                        Style::default()
-                            .fg(Theme::default().foreground)
-                            .bg(Theme::default().current_line),
-                    )))
+                    Style::default().fg(theme.foreground).bg(theme.current_line)
                 } else {
-                    ListItem::new(Line::from(Span::styled(
-                        item,
-                        Style::default()
-                            .fg(Theme::default().foreground)
-                            .bg(Theme::default().selection),
-                    )))
+                    Style::default().fg(theme.foreground).bg(theme.selection)
+                };
+                let mut spans = Vec::new();
+                if app.marked_services.contains(item) {
+                    spans.push(Span::styled("✓ ", style.fg(theme.green)));
                 }
+                if app.is_bookmarked(&app.profile, &app.cluster, item) {
+                    spans.push(Span::styled("★ ", style.fg(theme.yellow)));
+                }
+                if app.service_detail(item).is_some_and(service_is_degraded) {
+                    spans.push(Span::styled("● ", style.fg(theme.red)));
+                }
+                spans.push(Span::styled(item, style));
+                spans.extend(region_badge(
+                    app.service_detail(item).and_then(|s| s.service_arn()),
+                    style,
+                    &theme,
+                ));
+                ListItem::new(Line::from(spans))
             })
             .collect();
 
@@ -384,59 +1235,403 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
             .highlight_symbol(">> ");
         let service_list_scrollbar = Scrollbar::default()
             .orientation(ratatui::widgets::ScrollbarOrientation::VerticalRight)
-            .style(Style::default().bg(Theme::default().selection));
-
-        if !app.cluster.is_empty() && service_list.is_empty() {
-            let loading_block = Paragraph::new("Loading Services...")
-                .style(
-                    Style::default()
-                        .bg(Theme::default().selection)
-                        .fg(Theme::default().yellow),
-                )
+            .style(Style::default().bg(theme.selection));
+
+        if !app.cluster.is_empty() && service_list.is_empty() && app.services_loaded() {
+            let empty_block = Paragraph::new("No services in this cluster")
+                .style(Style::default().bg(theme.selection).fg(theme.comment))
+                .block(service_block);
+            frame.render_widget(empty_block, popup_chunks[2]);
+        } else if let Some(picker_error) = app
+            .picker_error
+            .as_ref()
+            .filter(|_| !app.cluster.is_empty() && service_list.is_empty())
+        {
+            let error_block = Paragraph::new(picker_error.as_str())
+                .style(Style::default().bg(theme.selection).fg(theme.red))
+                .wrap(Wrap { trim: false })
+                .block(service_block);
+            frame.render_widget(error_block, popup_chunks[2]);
+        } else if !app.cluster.is_empty() && service_list.is_empty() {
+            let loading_text = match app.service_load_progress {
+                Some((loaded, total)) if total > 0 => {
+                    format!("Loading Services... {}/{} described", loaded, total)
+                }
+                _ => "Loading Services...".to_string(),
+            };
+            let loading_block = Paragraph::new(loading_text)
+                .style(Style::default().bg(theme.selection).fg(theme.yellow))
                 .block(service_block);
             frame.render_widget(loading_block, popup_chunks[2]);
         } else {
             frame.render_stateful_widget(service_list, popup_chunks[2], &mut app.services.state);
+            sync_scrollbar(
+                &mut app.service_box.vertical_scroll_state,
+                &app.services.state,
+                app.services.items.len(),
+                popup_chunks[2],
+            );
             frame.render_stateful_widget(
                 service_list_scrollbar,
                 popup_chunks[2],
                 &mut app.service_box.vertical_scroll_state,
             );
         }
+        if !app.bookmarks.is_empty() {
+            let favorite_items: Vec<ListItem> = app
+                .favorites
+                .items
+                .iter()
+                .map(|item| {
+                    let style = if Some(item) == app.favorites.selected() {
+                        Style::default().fg(theme.foreground).bg(theme.current_line)
+                    } else {
+                        Style::default().fg(theme.foreground).bg(theme.selection)
+                    };
+                    ListItem::new(Line::from(Span::styled(item, style)))
+                })
+                .collect();
+            let favorite_list = List::new(favorite_items)
+                .block(favorite_block)
+                .highlight_symbol(">> ");
+            let favorite_list_scrollbar = Scrollbar::default()
+                .orientation(ratatui::widgets::ScrollbarOrientation::VerticalRight)
+                .style(Style::default().bg(theme.selection));
+            let favorite_area = app.favorite_box.area;
+            frame.render_stateful_widget(favorite_list, favorite_area, &mut app.favorites.state);
+            sync_scrollbar(
+                &mut app.favorite_box.vertical_scroll_state,
+                &app.favorites.state,
+                app.favorites.items.len(),
+                favorite_area,
+            );
+            frame.render_stateful_widget(
+                favorite_list_scrollbar,
+                favorite_area,
+                &mut app.favorite_box.vertical_scroll_state,
+            );
+        }
     }
     if let CurrentScreen::LogDetails = app.current_screen {
         frame.render_widget(Clear, frame.area()); //this clears the entire screen and anything already drawn
-        let popup_block = Block::default()
-            .title(" Log Details (ESC to go back) ")
+        let area = centered_rect(80, 80, frame.area());
+        let mut popup_block = Block::default()
+            .title(" Log Details - (j/k) scroll / (y) copy message / (ESC) back ")
             .padding(Padding::new(1, 1, 1, 1))
             .borders(Borders::ALL)
-            .style(Style::default().bg(Theme::default().selection));
+            .style(Style::default().bg(theme.selection));
+        if let Some(status) = &app.status_message {
+            popup_block = popup_block.title_bottom(
+                Line::from(Span::styled(
+                    format!(" {} ", status),
+                    Style::default().fg(theme.green),
+                ))
+                .right_aligned(),
+            );
+        }
 
-        let log_text = if let Some(selected) = app.service_events.selected() {
-            Text::styled(selected, Style::default().fg(Theme::default().foreground))
-        } else {
-            Text::styled("No log selected", Style::default().fg(Theme::default().red))
+        let log_text = match app.selected_event() {
+            Some((millis, tag, message)) => {
+                let timestamp = Utc.timestamp_millis_opt(millis).single();
+                let mut header = vec![Span::styled(
+                    timestamp.map_or(millis.to_string(), |timestamp| {
+                        format!(
+                            "{}  (local {})",
+                            timestamp.format("%Y-%m-%d %H:%M:%S%.3f UTC"),
+                            timestamp
+                                .with_timezone(&Local)
+                                .format("%Y-%m-%d %H:%M:%S%.3f")
+                        )
+                    }),
+                    Style::default().fg(theme.purple),
+                )];
+                if let Some(tag) = tag {
+                    header.push(Span::styled(
+                        format!("  [{}]", tag),
+                        Style::default().fg(theme.cyan),
+                    ));
+                }
+                let mut lines = vec![Line::from(header), Line::from("")];
+                lines.extend(
+                    message
+                        .lines()
+                        .map(|line| Line::styled(line.to_string(), theme.foreground)),
+                );
+                Text::from(lines)
+            }
+            None => Text::styled("No log selected", Style::default().fg(theme.red)),
         };
+        // keep the scroll within the wrapped text so `k` responds straight away
+        let inner_width = usize::from(area.width.saturating_sub(4)).max(1);
+        let inner_height = area.height.saturating_sub(4);
+        let wrapped_rows: usize = log_text
+            .lines
+            .iter()
+            .map(|line| line.width().div_ceil(inner_width).max(1))
+            .sum();
+        let max_scroll = u16::try_from(wrapped_rows)
+            .unwrap_or(u16::MAX)
+            .saturating_sub(inner_height);
+        app.detail_scroll = app.detail_scroll.min(max_scroll);
         // the `trim: false` will stop the text from being cut off when over the edge of the block
         let log_paragraph = Paragraph::new(log_text)
             .block(popup_block)
-            .wrap(Wrap { trim: false });
+            .wrap(Wrap { trim: false })
+            .scroll((app.detail_scroll, 0));
 
-        let area = centered_rect(80, 80, frame.area());
         frame.render_widget(log_paragraph, area);
     }
 
+    if let CurrentScreen::Deployments = app.current_screen {
+        frame.render_widget(Clear, frame.area()); //this clears the entire screen and anything already drawn
+        let popup_block = Block::default()
+            .title(format!(
+                " Deployments - {} (ESC to go back) ",
+                app.service.join(", ")
+            ))
+            .padding(Padding::new(1, 1, 1, 1))
+            .borders(Borders::ALL)
+            .style(Style::default().bg(theme.selection));
+
+        let deployment_text = if let Some(error) = &app.deployments_error {
+            Text::styled(error.clone(), Style::default().fg(theme.red))
+        } else if app.deployments_refreshed.is_none() {
+            Text::styled("Loading Deployments...", Style::default().fg(theme.comment))
+        } else if app.deployments.is_empty() {
+            Text::styled(
+                "No deployments for this service",
+                Style::default().fg(theme.comment),
+            )
+        } else {
+            let mut lines = Vec::new();
+            for (service, deployments) in &app.deployments {
+                if app.deployments.len() > 1 {
+                    lines.push(Line::styled(
+                        service.clone(),
+                        Style::default().fg(theme.green),
+                    ));
+                }
+                for deployment in deployments {
+                    let rollout_state = deployment
+                        .rollout_state()
+                        .map(|state| state.as_str())
+                        .unwrap_or("UNKNOWN");
+                    let state_color = match deployment.rollout_state() {
+                        Some(DeploymentRolloutState::Completed) => theme.green,
+                        Some(DeploymentRolloutState::Failed) => theme.red,
+                        _ => theme.yellow,
+                    };
+                    lines.push(Line::from(vec![
+                        Span::styled(
+                            format!("{:<12}", rollout_state),
+                            Style::default().fg(state_color),
+                        ),
+                        Span::styled(
+                            format!(
+                                "{} - running {}/{} pending {} failed {}",
+                                deployment.status().unwrap_or_default(),
+                                deployment.running_count(),
+                                deployment.desired_count(),
+                                deployment.pending_count(),
+                                deployment.failed_tasks(),
+                            ),
+                            Style::default().fg(theme.foreground),
+                        ),
+                    ]));
+                    lines.push(Line::styled(
+                        format!(
+                            "  {}",
+                            deployment
+                                .task_definition()
+                                .and_then(|arn| arn.rsplit('/').next())
+                                .unwrap_or_default()
+                        ),
+                        Style::default().fg(theme.comment),
+                    ));
+                    if let Some(reason) = deployment.rollout_state_reason() {
+                        lines.push(Line::styled(
+                            format!("  {}", reason),
+                            Style::default().fg(theme.comment),
+                        ));
+                    }
+                }
+            }
+            Text::from(lines)
+        };
+        let deployment_paragraph = Paragraph::new(deployment_text)
+            .block(popup_block)
+            .wrap(Wrap { trim: false });
+
+        let area = centered_rect(80, 60, frame.area());
+        frame.render_widget(deployment_paragraph, area);
+    }
+
+    if let CurrentScreen::LogStreams = app.current_screen {
+        frame.render_widget(Clear, frame.area());
+        let popup_block = Block::default()
+            .title(format!(
+                " Log Streams - {} - (Enter) view / (Backspace) all streams / (ESC) back ",
+                app.log_streams_group.as_deref().unwrap_or_default()
+            ))
+            .padding(Padding::new(1, 1, 1, 1))
+            .borders(Borders::ALL)
+            .style(Style::default().bg(theme.selection));
+        let area = centered_rect(80, 60, frame.area());
+        if let Some(error) = &app.log_streams_error {
+            let error_paragraph = Paragraph::new(error.as_str())
+                .style(Style::default().fg(theme.red))
+                .block(popup_block)
+                .wrap(Wrap { trim: false });
+            frame.render_widget(error_paragraph, area);
+        } else if app.log_streams.items.is_empty() {
+            let message = if app.log_streams_refreshed.is_none() {
+                "Loading Log Streams..."
+            } else {
+                "No log streams in this group"
+            };
+            let empty_paragraph = Paragraph::new(message)
+                .style(Style::default().fg(theme.comment))
+                .block(popup_block);
+            frame.render_widget(empty_paragraph, area);
+        } else {
+            let now = Utc::now();
+            let current = app.log_stream.as_ref().map(|(_, stream)| stream.as_str());
+            let items: Vec<ListItem> = app
+                .log_streams
+                .items
+                .iter()
+                .map(|stream| {
+                    let last_event = stream
+                        .last_event
+                        .map_or("-".to_string(), |millis| app.format_timestamp(millis, now));
+                    let color = if current == Some(stream.name.as_str()) {
+                        theme.green
+                    } else {
+                        theme.foreground
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("{}  ", last_event),
+                            Style::default().fg(theme.comment),
+                        ),
+                        Span::styled(stream.name.clone(), Style::default().fg(color)),
+                    ]))
+                })
+                .collect();
+            let list = List::new(items).block(popup_block).highlight_symbol(">> ");
+            frame.render_stateful_widget(list, area, &mut app.log_streams.state);
+        }
+    }
+
+    if let CurrentScreen::StoppedTasks = app.current_screen {
+        frame.render_widget(Clear, frame.area()); //this clears the entire screen and anything already drawn
+        let popup_block = Block::default()
+            .title(format!(
+                " Stopped Tasks - {} - (f) {} (ESC to go back) ",
+                app.service.join(", "),
+                if app.crashed_only {
+                    "crashed only"
+                } else {
+                    "all stopped"
+                }
+            ))
+            .padding(Padding::new(1, 1, 1, 1))
+            .borders(Borders::ALL)
+            .style(Style::default().bg(theme.selection));
+
+        let task_text = if let Some(error) = &app.stopped_tasks_error {
+            Text::styled(error.clone(), Style::default().fg(theme.red))
+        } else if app.stopped_tasks_refreshed.is_none() {
+            Text::styled(
+                "Loading Stopped Tasks...",
+                Style::default().fg(theme.comment),
+            )
+        } else {
+            let now = Utc::now();
+            let mut lines = Vec::new();
+            for (service, tasks) in &app.stopped_tasks {
+                if app.stopped_tasks.len() > 1 {
+                    lines.push(Line::styled(
+                        service.clone(),
+                        Style::default().fg(theme.green),
+                    ));
+                }
+                for task in tasks
+                    .iter()
+                    .filter(|task| !app.crashed_only || task_crashed(task))
+                {
+                    let stopped_at = task
+                        .stopped_at()
+                        .and_then(|at| at.to_millis().ok())
+                        .map_or("-".to_string(), |millis| app.format_timestamp(millis, now));
+                    let task_color = if task_crashed(task) {
+                        theme.red
+                    } else {
+                        theme.foreground
+                    };
+                    lines.push(Line::from(vec![
+                        Span::styled(
+                            format!("{}  ", stopped_at),
+                            Style::default().fg(theme.comment),
+                        ),
+                        Span::styled(
+                            format!(
+                                "{} - {}",
+                                task.task_arn()
+                                    .and_then(|arn| arn.rsplit('/').next())
+                                    .unwrap_or_default(),
+                                task.stopped_reason().unwrap_or("no stopped reason")
+                            ),
+                            Style::default().fg(task_color),
+                        ),
+                    ]));
+                    for container in task.containers() {
+                        let exit_code = container
+                            .exit_code()
+                            .map_or("-".to_string(), |code| code.to_string());
+                        lines.push(Line::styled(
+                            format!(
+                                "  {} exit {}{}",
+                                container.name().unwrap_or_default(),
+                                exit_code,
+                                container
+                                    .reason()
+                                    .map(|reason| format!(" - {}", reason))
+                                    .unwrap_or_default()
+                            ),
+                            Style::default().fg(theme.comment),
+                        ));
+                    }
+                }
+            }
+            if lines.is_empty() {
+                Text::styled(
+                    "No recently stopped tasks",
+                    Style::default().fg(theme.comment),
+                )
+            } else {
+                Text::from(lines)
+            }
+        };
+        let task_paragraph = Paragraph::new(task_text)
+            .block(popup_block)
+            .wrap(Wrap { trim: false });
+
+        let area = centered_rect(80, 60, frame.area());
+        frame.render_widget(task_paragraph, area);
+    }
+
     if let CurrentScreen::Exiting = app.current_screen {
         frame.render_widget(Clear, frame.area()); //this clears the entire screen and anything already drawn
         let popup_block = Block::default()
             .title(" Exit LazyLogger ")
             .padding(Padding::new(2, 2, 2, 2))
             .borders(Borders::ALL)
-            .style(Style::default().bg(Theme::default().selection));
+            .style(Style::default().bg(theme.selection));
 
         let exit_text = Text::styled(
             "Are you sure you want to exit? (y/n)",
-            Style::default().fg(Theme::default().red),
+            Style::default().fg(theme.red),
         );
         // the `trim: false` will stop the text from being cut off when over the edge of the block
         let exit_paragraph = Paragraph::new(exit_text)
@@ -446,4 +1641,63 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
         let area = centered_rect(60, 25, frame.area());
         frame.render_widget(exit_paragraph, area);
     }
+
+    if app.show_api_log {
+        let area = centered_rect(70, 50, frame.area());
+        frame.render_widget(Clear, area);
+        let calls = app.api_log.calls();
+        let lines: Vec<Line> = if calls.is_empty() {
+            vec![Line::styled(
+                "No AWS calls yet",
+                Style::default().fg(theme.comment),
+            )]
+        } else {
+            calls
+                .iter()
+                .map(|call| {
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{}  ", call.at.format("%H:%M:%S")),
+                            Style::default().fg(theme.comment),
+                        ),
+                        Span::styled(
+                            format!("{:>6} ms  ", call.duration.as_millis()),
+                            Style::default().fg(theme.yellow),
+                        ),
+                        Span::styled(
+                            if call.ok { "ok    " } else { "failed" },
+                            Style::default().fg(if call.ok { theme.green } else { theme.red }),
+                        ),
+                        Span::styled(
+                            format!("  {}", call.name),
+                            Style::default().fg(theme.foreground),
+                        ),
+                    ])
+                })
+                .collect()
+        };
+        let api_log = Paragraph::new(lines).block(
+            Block::default()
+                .title(" AWS calls, newest first - (Ctrl-D) to close ")
+                .padding(Padding::new(1, 1, 1, 1))
+                .borders(Borders::ALL)
+                .style(Style::default().bg(theme.selection)),
+        );
+        frame.render_widget(api_log, area);
+    }
+
+    if let Some(error) = &app.error_popup {
+        let area = centered_rect(60, 40, frame.area());
+        frame.render_widget(Clear, area);
+        let popup_block = Block::default()
+            .title(" Permission denied - any key to dismiss ")
+            .padding(Padding::new(2, 2, 1, 1))
+            .borders(Borders::ALL)
+            .style(Style::default().bg(theme.selection));
+        let error_paragraph =
+            Paragraph::new(Text::styled(error.as_str(), Style::default().fg(theme.red)))
+                .block(popup_block)
+                .wrap(Wrap { trim: false });
+        frame.render_widget(error_paragraph, area);
+    }
 }